@@ -27,6 +27,576 @@ impl Value {
         }
     }
 
+    /// Looks up a value by JSON Pointer (RFC 6901), e.g. `/users/0/name`.
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .try_fold(self, |target, token| match target {
+                Value::Object(object) => object.get(token.as_str()),
+                Value::Array(array) => token.parse::<usize>().ok().and_then(|i| array.get(i)),
+                _ => None,
+            })
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .try_fold(self, |target, token| match target {
+                Value::Object(object) => object.get_mut(token.as_str()),
+                Value::Array(array) => token
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(move |i| array.get_mut(i)),
+                _ => None,
+            })
+    }
+
+    /// Looks up a value by dot-separated path, e.g. `users.0.name`. Numeric segments index
+    /// arrays; the empty path is the value itself.
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        if path.is_empty() {
+            return Some(self);
+        }
+
+        path.split('.')
+            .try_fold(self, |target, segment| match target {
+                Value::Object(object) => object.get(segment),
+                Value::Array(array) => segment.parse::<usize>().ok().and_then(|i| array.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Mutable counterpart of `get_path`. Returns `None` when any segment is missing,
+    /// without creating it; see `pointer_or_create` for that.
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut Value> {
+        if path.is_empty() {
+            return Some(self);
+        }
+
+        path.split('.')
+            .try_fold(self, |target, segment| match target {
+                Value::Object(object) => object.get_mut(segment),
+                Value::Array(array) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(move |i| array.get_mut(i)),
+                _ => None,
+            })
+    }
+
+    /// Like `pointer_mut`, but creates whatever is missing along the way: `null` segments
+    /// become objects (or arrays when the next token is numeric), arrays are padded with
+    /// `null`, and the target itself starts as `null`. Panics on a malformed pointer or
+    /// when the path runs through a scalar.
+    pub fn pointer_or_create(&mut self, pointer: &str) -> &mut Value {
+        if pointer.is_empty() {
+            return self;
+        }
+        if !pointer.starts_with('/') {
+            panic!("Unable to resolve a JSON Pointer that does not start with '/'");
+        }
+
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .fold(self, |target, token| {
+                let index = token.parse::<usize>().ok();
+                if matches!(target, Value::Null | Value::Undefined) {
+                    *target = match index {
+                        Some(_) => Value::from(Vec::<Value>::new()),
+                        None => Value::from(Object::default()),
+                    };
+                }
+
+                match (target, index) {
+                    (Value::Object(Object::BTreeMap(map)), _) => {
+                        map.entry(ValueKey::from(token)).or_insert(Value::Null)
+                    }
+                    (Value::Object(Object::HashMap(map)), _) => {
+                        map.entry(ValueKey::from(token)).or_insert(Value::Null)
+                    }
+                    (Value::Array(array), Some(index)) => {
+                        if array.values.len() <= index {
+                            array.values.resize(index + 1, Value::Null);
+                        }
+                        &mut array.values[index]
+                    }
+                    _ => panic!(
+                        "Unable to create a path through a type other than an object or array"
+                    ),
+                }
+            })
+    }
+
+    /// Adds `by` to the number at `pointer`, creating the path (see `pointer_or_create`) and
+    /// starting from zero when the leaf is missing or `null`. Panics if the leaf holds
+    /// anything other than a number.
+    pub fn increment(&mut self, pointer: &str, by: Number) {
+        let target = self.pointer_or_create(pointer);
+        let current = match target {
+            Value::Null | Value::Undefined => Number::from(0i64),
+            Value::Number(number) => number.clone(),
+            _ => panic!("Unable to increment a type other than a number"),
+        };
+        *target = Value::Number(current.sum_with(&by));
+    }
+
+    /// Applies `f` to the existing node at `pointer`, returning false (without calling `f`)
+    /// when there is no such node.
+    pub fn update_at<F>(&mut self, pointer: &str, f: F) -> bool
+    where
+        F: FnOnce(&mut Value),
+    {
+        match self.pointer_mut(pointer) {
+            Some(target) => {
+                f(target);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Applies `f` to every existing node matching `pattern`, a JSON Pointer in which a `*`
+    /// token matches any array index or object key, e.g. `/users/*/name`. Returns the
+    /// number of nodes updated.
+    pub fn update_matching<F>(&mut self, pattern: &str, mut f: F) -> usize
+    where
+        F: FnMut(&mut Value),
+    {
+        if !pattern.is_empty() && !pattern.starts_with('/') {
+            return 0;
+        }
+        let tokens = pattern
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .collect::<Vec<_>>();
+        self.update_matching_inner(&tokens, &mut f)
+    }
+
+    fn update_matching_inner<F>(&mut self, tokens: &[String], f: &mut F) -> usize
+    where
+        F: FnMut(&mut Value),
+    {
+        let (token, rest) = match tokens.split_first() {
+            Some(split) => split,
+            None => {
+                f(self);
+                return 1;
+            }
+        };
+
+        match self {
+            Value::Array(array) if token == "*" => array
+                .values
+                .iter_mut()
+                .map(|item| item.update_matching_inner(rest, f))
+                .sum(),
+            Value::Array(array) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| array.values.get_mut(index))
+                .map_or(0, |item| item.update_matching_inner(rest, f)),
+            Value::Object(Object::BTreeMap(map)) if token == "*" => map
+                .values_mut()
+                .map(|item| item.update_matching_inner(rest, f))
+                .sum(),
+            Value::Object(Object::HashMap(map)) if token == "*" => map
+                .values_mut()
+                .map(|item| item.update_matching_inner(rest, f))
+                .sum(),
+            Value::Object(object) => object
+                .get_mut(token.as_str())
+                .map_or(0, |item| item.update_matching_inner(rest, f)),
+            _ => 0,
+        }
+    }
+
+    /// Returns true if `needle` is structurally equal to this value or to any value nested in it.
+    pub fn contains_value(&self, needle: &Value) -> bool {
+        if self == needle {
+            return true;
+        }
+        match self {
+            Value::Array(array) => array.into_iter().any(|item| item.contains_value(needle)),
+            Value::Object(object) => object
+                .values()
+                .iter()
+                .any(|item| item.contains_value(needle)),
+            _ => false,
+        }
+    }
+
+    /// Returns true if `other` structurally contains this value: every entry of an object must
+    /// be present in `other` with a value it is itself a subset of, and every element of an
+    /// array must be a subset of some element of `other`. Other values compare by equality.
+    pub fn is_subset_of(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Object(object), Value::Object(other)) => object.iter().all(|(key, value)| {
+                let candidate = match other {
+                    Object::BTreeMap(map) => map.get(key),
+                    Object::HashMap(map) => map.get(key),
+                };
+                candidate.is_some_and(|candidate| value.is_subset_of(candidate))
+            }),
+            (Value::Array(array), Value::Array(other)) => array.into_iter().all(|value| {
+                other
+                    .into_iter()
+                    .any(|candidate| value.is_subset_of(candidate))
+            }),
+            _ => self == other,
+        }
+    }
+
+    /// Returns true if an object at any depth has `key`.
+    pub fn deep_contains_key(&self, key: &str) -> bool {
+        match self {
+            Value::Object(object) => {
+                object.contains_key(&key)
+                    || object
+                        .values()
+                        .iter()
+                        .any(|value| value.deep_contains_key(key))
+            }
+            Value::Array(array) => array.into_iter().any(|value| value.deep_contains_key(key)),
+            _ => false,
+        }
+    }
+
+    /// Collects the values stored under `key` in every object at any depth, parents first.
+    pub fn deep_get_all(&self, key: &str) -> Vec<&Value> {
+        let mut found = Vec::new();
+        self.deep_get_all_inner(key, &mut found);
+        found
+    }
+
+    fn deep_get_all_inner<'a>(&'a self, key: &str, found: &mut Vec<&'a Value>) {
+        match self {
+            Value::Object(object) => {
+                if let Some(value) = object.get(key) {
+                    found.push(value);
+                }
+                for value in object.values() {
+                    value.deep_get_all_inner(key, found);
+                }
+            }
+            Value::Array(array) => {
+                for value in array {
+                    value.deep_get_all_inner(key, found);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the JSON Pointers of every place where `needle` occurs, the root being `""`.
+    pub fn find_paths(&self, needle: &Value) -> Vec<String> {
+        let mut paths = Vec::new();
+        self.find_paths_inner(needle, String::new(), &mut paths);
+        paths
+    }
+
+    fn find_paths_inner(&self, needle: &Value, path: String, paths: &mut Vec<String>) {
+        if self == needle {
+            paths.push(path.clone());
+        }
+        match self {
+            Value::Array(array) => {
+                for (index, item) in array.into_iter().enumerate() {
+                    item.find_paths_inner(needle, format!("{}/{}", path, index), paths);
+                }
+            }
+            Value::Object(object) => {
+                for (key, item) in object.iter() {
+                    let token = escape_pointer_token(&key.to_string());
+                    item.find_paths_inner(needle, format!("{}/{}", path, token), paths);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns every string leaf, depth-first. Object entries are visited in key order so
+    /// the result does not depend on the map backing.
+    pub fn collect_strings(&self) -> Vec<&str> {
+        let mut strings = Vec::new();
+        self.collect_strings_inner(&mut String::new(), &mut |_, string| strings.push(string));
+        strings
+    }
+
+    /// Like `collect_strings`, pairing each string with its JSON Pointer.
+    pub fn collect_strings_with_paths(&self) -> Vec<(String, &str)> {
+        let mut strings = Vec::new();
+        self.collect_strings_inner(&mut String::new(), &mut |path, string| {
+            strings.push((path.to_string(), string))
+        });
+        strings
+    }
+
+    fn collect_strings_inner<'a>(&'a self, path: &mut String, f: &mut dyn FnMut(&str, &'a str)) {
+        let len = path.len();
+        match self {
+            Value::String(string) => f(path, string.as_str()),
+            Value::Array(array) => {
+                for (index, item) in array.into_iter().enumerate() {
+                    path.push_str(&format!("/{}", index));
+                    item.collect_strings_inner(path, f);
+                    path.truncate(len);
+                }
+            }
+            Value::Object(object) => {
+                let mut entries = object.iter().collect::<Vec<_>>();
+                entries.sort_by_key(|(key, _)| key.to_string());
+                for (key, item) in entries {
+                    path.push('/');
+                    path.push_str(&escape_pointer_token(&key.to_string()));
+                    item.collect_strings_inner(path, f);
+                    path.truncate(len);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Visits every node depth-first, parents before children, passing its JSON Pointer
+    /// (the root being `""`). The walk stops as soon as `f` returns `Break`, whose value
+    /// is returned.
+    pub fn try_for_each<B, F>(&self, mut f: F) -> std::ops::ControlFlow<B>
+    where
+        F: FnMut(&str, &Value) -> std::ops::ControlFlow<B>,
+    {
+        self.try_for_each_inner(&mut String::new(), &mut f)
+    }
+
+    fn try_for_each_inner<B, F>(&self, path: &mut String, f: &mut F) -> std::ops::ControlFlow<B>
+    where
+        F: FnMut(&str, &Value) -> std::ops::ControlFlow<B>,
+    {
+        f(path, self)?;
+        let len = path.len();
+        match self {
+            Value::Array(array) => {
+                for (index, item) in array.into_iter().enumerate() {
+                    path.push_str(&format!("/{}", index));
+                    item.try_for_each_inner(path, f)?;
+                    path.truncate(len);
+                }
+            }
+            Value::Object(object) => {
+                for (key, item) in object.iter() {
+                    path.push('/');
+                    path.push_str(&escape_pointer_token(&key.to_string()));
+                    item.try_for_each_inner(path, f)?;
+                    path.truncate(len);
+                }
+            }
+            _ => {}
+        }
+        std::ops::ControlFlow::Continue(())
+    }
+
+    /// Returns the boolean at `pointer`, or `default` when it is missing or not a boolean.
+    pub fn get_bool_or(&self, pointer: &str, default: bool) -> bool {
+        match self.pointer(pointer) {
+            Some(Value::Boolean(value)) => *value,
+            _ => default,
+        }
+    }
+
+    /// Returns the integer at `pointer`, or `default` when it is missing, not an
+    /// integer, or out of `i64` range.
+    pub fn get_i64_or(&self, pointer: &str, default: i64) -> i64 {
+        match self.pointer(pointer) {
+            Some(Value::Number(number)) => number
+                .integer_as_i128()
+                .and_then(|value| i64::try_from(value).ok())
+                .unwrap_or(default),
+            _ => default,
+        }
+    }
+
+    /// Returns the number at `pointer` as `f64`, or `default` when it is missing or not a number.
+    pub fn get_f64_or(&self, pointer: &str, default: f64) -> f64 {
+        match self.pointer(pointer) {
+            Some(Value::Number(number)) => number.to_f64().unwrap_or(default),
+            _ => default,
+        }
+    }
+
+    /// Returns the string at `pointer`, or `default` when it is missing or not a string.
+    pub fn get_str_or<'a>(&'a self, pointer: &str, default: &'a str) -> &'a str {
+        match self.pointer(pointer) {
+            Some(Value::String(value)) => value.as_str(),
+            _ => default,
+        }
+    }
+
+    /// Deep-merges `other` into this value: objects are merged key by key, recursively;
+    /// any other combination replaces this value with `other`.
+    pub fn deep_merge(&mut self, other: Value) {
+        match (self, other) {
+            (Value::Object(target), Value::Object(source)) => {
+                let entries: Vec<(ValueKey, Value)> = match source {
+                    Object::BTreeMap(map) => map.into_iter().collect(),
+                    Object::HashMap(map) => map.into_iter().collect(),
+                };
+                for (key, value) in entries {
+                    let existing = match target {
+                        Object::BTreeMap(map) => map.get_mut(&key),
+                        Object::HashMap(map) => map.get_mut(&key),
+                    };
+                    match existing {
+                        Some(existing) => existing.deep_merge(value),
+                        None => match target {
+                            Object::BTreeMap(map) => {
+                                map.insert(key, value);
+                            }
+                            Object::HashMap(map) => {
+                                map.insert(key, value);
+                            }
+                        },
+                    }
+                }
+            }
+            (target, other) => *target = other,
+        }
+    }
+
+    /// Deep-merges a sequence of layers from left to right, so later layers win.
+    /// Returns `Null` for an empty slice.
+    pub fn merge_all(values: &[Value]) -> Value {
+        let mut iter = values.iter();
+        let mut merged = match iter.next() {
+            Some(first) => first.clone(),
+            None => return Value::Null,
+        };
+        for value in iter {
+            merged.deep_merge(value.clone());
+        }
+        merged
+    }
+
+    /// Deep-merges the object elements of an array, left to right, into a single object.
+    /// Other elements are skipped; the result is an empty object when there are none or
+    /// the value is not an array.
+    pub fn merge_array_of_objects(&self) -> Value {
+        let mut merged = Value::Object(Object::default());
+        if let Value::Array(array) = self {
+            for item in array.into_iter().filter(|item| item.is_object()) {
+                merged.deep_merge(item.clone());
+            }
+        }
+        merged
+    }
+
+    /// Copies every key of `defaults` that this object lacks, recursing into objects present
+    /// on both sides. Existing values, including `Null`, are never overwritten; non-object
+    /// values are left as they are.
+    pub fn fill_defaults(&mut self, defaults: &Value) {
+        let (target, defaults) = match (self, defaults) {
+            (Value::Object(target), Value::Object(defaults)) => (target, defaults),
+            _ => return,
+        };
+
+        for (key, default) in defaults.iter() {
+            let existing = match target {
+                Object::BTreeMap(map) => map.get_mut(key),
+                Object::HashMap(map) => map.get_mut(key),
+            };
+            match existing {
+                Some(existing) => existing.fill_defaults(default),
+                None => match target {
+                    Object::BTreeMap(map) => {
+                        map.insert(key.clone(), default.clone());
+                    }
+                    Object::HashMap(map) => {
+                        map.insert(key.clone(), default.clone());
+                    }
+                },
+            }
+        }
+    }
+
+    /// Pairs two objects up key by key: every key present on either side maps to a
+    /// `[self_value, other_value]` array, with `Null` standing in for the side that lacks
+    /// it. A non-object counts as an object without keys.
+    pub fn zip(&self, other: &Value) -> Value {
+        let mut pairs = std::collections::BTreeMap::new();
+        if let Value::Object(object) = self {
+            for (key, value) in object.iter() {
+                pairs.insert(key.clone(), vec![value.clone(), Value::Null]);
+            }
+        }
+        if let Value::Object(object) = other {
+            for (key, value) in object.iter() {
+                pairs
+                    .entry(key.clone())
+                    .or_insert_with(|| vec![Value::Null, Value::Null])[1] = value.clone();
+            }
+        }
+
+        let pairs = pairs
+            .into_iter()
+            .map(|(key, pair)| (key, Value::from(pair)));
+        let mut zipped = Object::default();
+        match &mut zipped {
+            Object::BTreeMap(map) => map.extend(pairs),
+            Object::HashMap(map) => map.extend(pairs),
+        }
+        Value::Object(zipped)
+    }
+
+    /// Keeps what two values have in common. Objects keep only the keys present in both,
+    /// with their values intersected recursively; arrays are intersected position by
+    /// position over their common length. Equal scalars are kept and anything else,
+    /// including values of different types, becomes `Null`.
+    pub fn intersect(&self, other: &Value) -> Value {
+        match (self, other) {
+            (Value::Object(left), Value::Object(right)) => {
+                let shared = left.iter().filter_map(|(key, value)| {
+                    let other = match right {
+                        Object::BTreeMap(map) => map.get(key),
+                        Object::HashMap(map) => map.get(key),
+                    }?;
+                    Some((key.clone(), value.intersect(other)))
+                });
+                let mut intersection = Object::default();
+                match &mut intersection {
+                    Object::BTreeMap(map) => map.extend(shared),
+                    Object::HashMap(map) => map.extend(shared),
+                }
+                Value::Object(intersection)
+            }
+            (Value::Array(left), Value::Array(right)) => Value::from(
+                left.into_iter()
+                    .zip(right)
+                    .map(|(left, right)| left.intersect(right))
+                    .collect::<Vec<_>>(),
+            ),
+            (left, right) if left == right => left.clone(),
+            _ => Value::Null,
+        }
+    }
+
     pub fn clean(&mut self) {
         match self {
             Value::Array(array) => array.clean(),
@@ -128,6 +698,16 @@ impl Value {
         }
     }
 
+    /// Reads a field that may hold one value or a list of them: returns the elements of
+    /// an array, the value itself for any other type, and nothing for `Null` or `Undefined`.
+    pub fn as_array_coerced(&self) -> Vec<&Value> {
+        match self {
+            Value::Array(array) => array.into_iter().collect(),
+            Value::Null | Value::Undefined => Vec::new(),
+            value => vec![value],
+        }
+    }
+
     pub fn as_object(&self) -> Option<&Object> {
         match self {
             Value::Object(object) => Some(object),
@@ -205,6 +785,35 @@ impl Value {
         }
     }
 
+    /// Returns the name of the variant, e.g. `"object"` or `"datetime"`.
+    pub fn type_name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    fn type_mismatch(&self, expected: &'static str) -> Error {
+        Error::TypeMismatch {
+            expected,
+            found: self.type_name(),
+        }
+    }
+
+    pub fn expect_object(&self) -> Result<&Object, Error> {
+        self.as_object().ok_or_else(|| self.type_mismatch("object"))
+    }
+
+    pub fn expect_array(&self) -> Result<&Array, Error> {
+        self.as_array().ok_or_else(|| self.type_mismatch("array"))
+    }
+
+    pub fn expect_string(&self) -> Result<&StringB, Error> {
+        self.as_string_b()
+            .ok_or_else(|| self.type_mismatch("string"))
+    }
+
+    pub fn expect_number(&self) -> Result<&Number, Error> {
+        self.as_number().ok_or_else(|| self.type_mismatch("number"))
+    }
+
     pub fn push<T: ToValueBehavior>(&mut self, value: T) {
         match self {
             Value::Array(array) => array.push(value.to_value()),
@@ -212,781 +821,2461 @@ impl Value {
         }
     }
 
-    pub fn insert<T, V>(&mut self, key: T, value: V) -> Option<Value>
-    where
-        T: ValueKeyBehavior,
-        V: ToValueBehavior,
-    {
+    /// Inserts a value at the front of an array. Costs O(n), see `Array::push_front`.
+    pub fn array_push_front<T: ToValueBehavior>(&mut self, value: T) {
         match self {
-            Value::Object(o) => o.insert(key, value.to_value()),
-            _ => panic!("Unable to insert values ​​into a type other than an object"),
+            Value::Array(array) => array.push_front(value.to_value()),
+            _ => panic!("Unable to push values into a type other than an array"),
         }
     }
-}
 
-impl NumberBehavior for Value {
-    fn set_u8(&mut self, value: u8) {
+    /// Removes and returns the first element of an array. Costs O(n), see `Array::pop_front`.
+    pub fn array_pop_front(&mut self) -> Option<Value> {
         match self {
-            Value::Number(n) => n.set_u8(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => array.pop_front(),
+            _ => panic!("Unable to pop a value other than an array"),
         }
     }
 
-    fn set_u16(&mut self, value: u16) {
+    pub fn array_splice(
+        &mut self,
+        range: std::ops::Range<usize>,
+        replacement: Vec<Value>,
+    ) -> Vec<Value> {
         match self {
-            Value::Number(n) => n.set_u16(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => array.splice(range, replacement),
+            _ => panic!("Unable to splice a type other than an array"),
         }
     }
 
-    fn set_u32(&mut self, value: u32) {
+    pub fn array_dedup(&mut self) {
         match self {
-            Value::Number(n) => n.set_u32(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => array.dedup(),
+            _ => panic!("Unable to dedup a type other than an array"),
         }
     }
 
-    fn set_u64(&mut self, value: u64) {
+    pub fn array_dedup_by_key<T>(&mut self, key: T)
+    where
+        T: ValueKeyBehavior,
+    {
         match self {
-            Value::Number(n) => n.set_u64(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => array.dedup_by_key(key),
+            _ => panic!("Unable to dedup a type other than an array"),
         }
     }
 
-    fn set_u128(&mut self, value: u128) {
-        match self {
-            Value::Number(n) => n.set_u128(value),
-            _ => panic!("Unable to set a value other than a number"),
+    /// Reverses the elements of an array in place. Does nothing for other values.
+    pub fn array_reverse(&mut self) {
+        if let Value::Array(array) = self {
+            array.values.reverse();
         }
     }
 
-    fn set_i8(&mut self, value: i8) {
-        match self {
-            Value::Number(n) => n.set_i8(value),
-            _ => panic!("Unable to set a value other than a number"),
+    /// Rotates an array in place so the element at `n` comes first. `n` wraps around the
+    /// array length. Does nothing for other values.
+    pub fn array_rotate_left(&mut self, n: usize) {
+        if let Value::Array(array) = self {
+            if !array.values.is_empty() {
+                let n = n % array.values.len();
+                array.values.rotate_left(n);
+            }
         }
     }
 
-    fn set_i16(&mut self, value: i16) {
-        match self {
-            Value::Number(n) => n.set_i16(value),
-            _ => panic!("Unable to set a value other than a number"),
+    /// Rotates an array in place so the last `n` elements come first. `n` wraps around
+    /// the array length. Does nothing for other values.
+    pub fn array_rotate_right(&mut self, n: usize) {
+        if let Value::Array(array) = self {
+            if !array.values.is_empty() {
+                let n = n % array.values.len();
+                array.values.rotate_right(n);
+            }
         }
     }
 
-    fn set_i32(&mut self, value: i32) {
+    /// Returns overlapping windows of `size` elements, like `slice::windows`. Empty when the
+    /// value is not an array, is shorter than `size`, or `size` is zero.
+    pub fn array_windows(&self, size: usize) -> Vec<Value> {
         match self {
-            Value::Number(n) => n.set_i32(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) if size > 0 => array
+                .values
+                .windows(size)
+                .map(|window| Value::from(window.to_vec()))
+                .collect(),
+            _ => Vec::new(),
         }
     }
 
-    fn set_i64(&mut self, value: i64) {
+    /// Returns the position of the first element structurally equal to `needle`, or
+    /// `None` when there is no match or the value is not an array.
+    pub fn array_index_of(&self, needle: &Value) -> Option<usize> {
         match self {
-            Value::Number(n) => n.set_i64(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => array.values.iter().position(|item| item == needle),
+            _ => None,
         }
     }
 
-    fn set_i128(&mut self, value: i128) {
+    /// Like `array_index_of`, returning the position of the last match.
+    pub fn array_last_index_of(&self, needle: &Value) -> Option<usize> {
         match self {
-            Value::Number(n) => n.set_i128(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => array.values.iter().rposition(|item| item == needle),
+            _ => None,
         }
     }
 
-    fn set_f32(&mut self, value: f32) {
+    pub fn array_fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: Fn(B, &Value) -> B,
+    {
         match self {
-            Value::Number(n) => n.set_f32(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => array.into_iter().fold(init, f),
+            _ => panic!("Unable to fold a type other than an array"),
         }
     }
 
-    fn set_f64(&mut self, value: f64) {
+    pub fn array_reduce<F>(&self, f: F) -> Option<Value>
+    where
+        F: Fn(Value, &Value) -> Value,
+    {
         match self {
-            Value::Number(n) => n.set_f64(value),
-            _ => panic!("Unable to set a value other than a number"),
+            Value::Array(array) => {
+                let mut iter = array.into_iter();
+                let first = iter.next()?.clone();
+                Some(iter.fold(first, f))
+            }
+            _ => panic!("Unable to reduce a type other than an array"),
         }
     }
 
-    fn get_u8(&self) -> Option<u8> {
-        match self {
-            Value::Number(n) => n.get_u8(),
-            _ => panic!("Unable to get a value other than a number"),
-        }
+    /// Creates an empty object value pre-sized for `capacity` entries.
+    pub fn object_with_capacity(capacity: usize) -> Value {
+        Value::Object(Object::with_capacity(capacity))
     }
 
-    fn get_u16(&self) -> Option<u16> {
+    /// Returns the object's values, or an empty list for non-objects.
+    pub fn object_values(&self) -> Vec<&Value> {
         match self {
-            Value::Number(n) => n.get_u16(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Object(object) => object.values(),
+            _ => Vec::new(),
         }
     }
 
-    fn get_u32(&self) -> Option<u32> {
+    /// Returns the object's string-keyed entries, or an empty list for non-objects.
+    /// Numeric keys have no borrowed `&str` form and are left out.
+    pub fn object_entries(&self) -> Vec<(&str, &Value)> {
         match self {
-            Value::Number(n) => n.get_u32(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Object(object) => object
+                .iter()
+                .filter_map(|(key, value)| key.as_str().map(|key| (key, value)))
+                .collect(),
+            _ => Vec::new(),
         }
     }
 
-    fn get_u64(&self) -> Option<u64> {
-        match self {
-            Value::Number(n) => n.get_u64(),
-            _ => panic!("Unable to get a value other than a number"),
+    /// Looks up an object key ignoring case. An exact match is preferred over other matches.
+    pub fn object_get_ci(&self, key: &str) -> Option<&Value> {
+        let object = self.as_object()?;
+        if let Some(value) = object.get(key) {
+            return Some(value);
         }
+
+        let key = key.to_lowercase();
+        object
+            .iter()
+            .find(|(k, _)| k.to_string().to_lowercase() == key)
+            .map(|(_, v)| v)
     }
 
-    fn get_u128(&self) -> Option<u128> {
-        match self {
-            Value::Number(n) => n.get_u128(),
-            _ => panic!("Unable to get a value other than a number"),
+    pub fn object_get_ci_mut(&mut self, key: &str) -> Option<&mut Value> {
+        let object = self.as_object_mut()?;
+        if object.get(key).is_some() {
+            return object.get_mut(key);
         }
-    }
 
-    fn get_i8(&self) -> Option<i8> {
-        match self {
-            Value::Number(n) => n.get_i8(),
-            _ => panic!("Unable to get a value other than a number"),
+        let key = key.to_lowercase();
+        match object {
+            Object::BTreeMap(map) => map
+                .iter_mut()
+                .find(|(k, _)| k.to_string().to_lowercase() == key)
+                .map(|(_, v)| v),
+            Object::HashMap(map) => map
+                .iter_mut()
+                .find(|(k, _)| k.to_string().to_lowercase() == key)
+                .map(|(_, v)| v),
         }
     }
 
-    fn get_i16(&self) -> Option<i16> {
+    /// Consumes an object into its entries, sorted by key. See `Object::into_entries`.
+    pub fn into_entries(self) -> Vec<(String, Value)> {
         match self {
-            Value::Number(n) => n.get_i16(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Object(object) => object.into_entries(),
+            _ => panic!("Unable to get the entries of a type other than an object"),
         }
     }
 
-    fn get_i32(&self) -> Option<i32> {
-        match self {
-            Value::Number(n) => n.get_i32(),
-            _ => panic!("Unable to get a value other than a number"),
-        }
+    /// Builds an object value from entries. See `Object::from_entries`.
+    pub fn from_entries(entries: Vec<(String, Value)>) -> Value {
+        Value::Object(Object::from_entries(entries))
     }
 
-    fn get_i64(&self) -> Option<i64> {
+    /// Renames the key `from` to `to`, returning false if `from` is absent or this is not an
+    /// object. An existing `to` entry is overwritten. Objects keep no insertion order, so the
+    /// entry lands wherever the backing map places `to`.
+    pub fn object_rename_key(&mut self, from: &str, to: &str) -> bool {
+        let (from, to) = (ValueKey::from(from), ValueKey::from(to));
         match self {
-            Value::Number(n) => n.get_i64(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Object(Object::BTreeMap(map)) => match map.remove(&from) {
+                Some(value) => {
+                    map.insert(to, value);
+                    true
+                }
+                None => false,
+            },
+            Value::Object(Object::HashMap(map)) => match map.remove(&from) {
+                Some(value) => {
+                    map.insert(to, value);
+                    true
+                }
+                None => false,
+            },
+            _ => false,
         }
     }
 
-    fn get_i128(&self) -> Option<i128> {
+    /// Recursively renames every object key with `f`. When two keys map to the same name the
+    /// last one written wins; the colliding names are returned.
+    pub fn rename_keys<F>(&mut self, f: F) -> Vec<String>
+    where
+        F: Fn(&str) -> String,
+    {
+        let mut collisions = Vec::new();
+        self.rename_keys_inner(&f, &mut collisions);
+        collisions
+    }
+
+    fn rename_keys_inner<F>(&mut self, f: &F, collisions: &mut Vec<String>)
+    where
+        F: Fn(&str) -> String,
+    {
         match self {
-            Value::Number(n) => n.get_i128(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Object(object) => {
+                let entries: Vec<(ValueKey, Value)> = match object {
+                    Object::BTreeMap(map) => std::mem::take(map).into_iter().collect(),
+                    Object::HashMap(map) => std::mem::take(map).into_iter().collect(),
+                };
+
+                for (key, mut value) in entries {
+                    value.rename_keys_inner(f, collisions);
+                    let key = f(&key.to_string());
+                    if object.insert(key.clone(), value).is_some() {
+                        collisions.push(key);
+                    }
+                }
+            }
+            Value::Array(array) => {
+                for value in array {
+                    value.rename_keys_inner(f, collisions);
+                }
+            }
+            _ => {}
         }
     }
 
-    fn get_f32(&self) -> Option<f32> {
+    /// Recursively unifies number representations: integral floats become integers,
+    /// or, when `prefer_float` is set, integers become floats. Strings are left untouched.
+    pub fn normalize_numbers(&mut self, prefer_float: bool) {
         match self {
-            Value::Number(n) => n.get_f32(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Number(number) => {
+                if prefer_float {
+                    if !number.is_float() {
+                        if let Some(float) = number.to_f64() {
+                            *number = Number::from(float);
+                        }
+                    }
+                } else if number.is_float() {
+                    if let Some(float) = number.to_f64() {
+                        if float.fract() == 0.0
+                            && float >= i64::MIN as f64
+                            && float < i64::MAX as f64
+                        {
+                            *number = Number::from(float as i64);
+                        }
+                    }
+                }
+            }
+            Value::Array(array) => {
+                for value in array {
+                    value.normalize_numbers(prefer_float);
+                }
+            }
+            Value::Object(object) => match object {
+                Object::BTreeMap(map) => map
+                    .values_mut()
+                    .for_each(|value| value.normalize_numbers(prefer_float)),
+                Object::HashMap(map) => map
+                    .values_mut()
+                    .for_each(|value| value.normalize_numbers(prefer_float)),
+            },
+            _ => {}
         }
     }
 
-    fn get_f64(&self) -> Option<f64> {
+    /// Recursively removes object entries whose value is `Null`. When `strip_array_nulls`
+    /// is set, `Null` array elements are removed as well; when `prune_empty` is set, objects
+    /// and arrays left empty by the stripping are removed from their parent too.
+    pub fn strip_nulls(&mut self, strip_array_nulls: bool, prune_empty: bool) {
+        let keep = |value: &Value| {
+            !(value.is_null()
+                || (prune_empty && (value.is_object() || value.is_array()) && value.is_empty()))
+        };
+
         match self {
-            Value::Number(n) => n.get_f64(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Object(object) => match object {
+                Object::BTreeMap(map) => {
+                    map.values_mut()
+                        .for_each(|value| value.strip_nulls(strip_array_nulls, prune_empty));
+                    map.retain(|_, value| keep(value));
+                }
+                Object::HashMap(map) => {
+                    map.values_mut()
+                        .for_each(|value| value.strip_nulls(strip_array_nulls, prune_empty));
+                    map.retain(|_, value| keep(value));
+                }
+            },
+            Value::Array(array) => {
+                for value in array.values.iter_mut() {
+                    value.strip_nulls(strip_array_nulls, prune_empty);
+                }
+                if strip_array_nulls {
+                    array.values.retain(|value| keep(value));
+                } else if prune_empty {
+                    array.values.retain(|value| value.is_null() || keep(value));
+                }
+            }
+            _ => {}
         }
     }
 
-    fn get_u8_unsafe(&self) -> u8 {
+    /// Returns a minimal copy of the tree for sending: object entries that are `Null` or
+    /// `Undefined` are dropped, as are empty strings when `strip_empty_strings` is set, and
+    /// then any object or array that ends up (or already was) empty. Array elements are
+    /// only dropped when they are empty containers, so positions of scalars are kept.
+    pub fn compact(self, strip_empty_strings: bool) -> Value {
+        let keep = |value: &Value| match value {
+            Value::Null | Value::Undefined => false,
+            Value::String(string) => !(strip_empty_strings && string.is_empty()),
+            Value::Object(object) => !object.is_empty(),
+            Value::Array(array) => !array.is_empty(),
+            _ => true,
+        };
+
         match self {
-            Value::Number(n) => n.get_u8_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Object(object) => {
+                let object = match object {
+                    Object::BTreeMap(map) => Object::BTreeMap(
+                        map.into_iter()
+                            .map(|(key, value)| (key, value.compact(strip_empty_strings)))
+                            .filter(|(_, value)| keep(value))
+                            .collect(),
+                    ),
+                    Object::HashMap(map) => Object::HashMap(
+                        map.into_iter()
+                            .map(|(key, value)| (key, value.compact(strip_empty_strings)))
+                            .filter(|(_, value)| keep(value))
+                            .collect(),
+                    ),
+                };
+                Value::Object(object)
+            }
+            Value::Array(array) => Value::from(
+                array
+                    .values
+                    .into_iter()
+                    .map(|value| value.compact(strip_empty_strings))
+                    .filter(|value| match value {
+                        Value::Object(_) | Value::Array(_) => keep(value),
+                        _ => true,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            value => value,
+        }
+    }
+
+    /// Concatenates nested arrays into their parent up to `depth` levels, like JS `Array.flat`.
+    pub fn flatten_array(&self, depth: usize) -> Value {
+        fn flatten_into(values: &Array, depth: usize, out: &mut Vec<Value>) {
+            for value in values {
+                match value {
+                    Value::Array(inner) if depth > 0 => flatten_into(inner, depth - 1, out),
+                    _ => out.push(value.clone()),
+                }
+            }
         }
-    }
 
-    fn get_u16_unsafe(&self) -> u16 {
         match self {
-            Value::Number(n) => n.get_u16_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Array(array) => {
+                let mut out = Vec::with_capacity(array.len());
+                flatten_into(array, depth, &mut out);
+                Value::from(out)
+            }
+            _ => panic!("Unable to flatten a type other than an array"),
         }
     }
 
-    fn get_u32_unsafe(&self) -> u32 {
+    /// Maps each element with `f`, keeping only the `Some` results, in a new array.
+    pub fn filter_map_array<F>(&self, f: F) -> Value
+    where
+        F: Fn(&Value) -> Option<Value>,
+    {
         match self {
-            Value::Number(n) => n.get_u32_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Array(array) => Value::from(array.into_iter().filter_map(f).collect::<Vec<_>>()),
+            _ => panic!("Unable to filter_map a type other than an array"),
         }
     }
 
-    fn get_u64_unsafe(&self) -> u64 {
+    /// Splits an array into the elements that satisfy `f` and those that don't,
+    /// both in their original order.
+    pub fn partition_array<F>(&self, f: F) -> (Value, Value)
+    where
+        F: Fn(&Value) -> bool,
+    {
         match self {
-            Value::Number(n) => n.get_u64_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Array(array) => {
+                let (matched, rest): (Vec<Value>, Vec<Value>) =
+                    array.into_iter().cloned().partition(|value| f(value));
+                (Value::from(matched), Value::from(rest))
+            }
+            _ => panic!("Unable to partition a type other than an array"),
         }
     }
 
-    fn get_u128_unsafe(&self) -> u128 {
+    /// Sorts an array in place by the key `key_fn` extracts from each element. The sort is
+    /// stable, so elements with equal keys keep their relative order.
+    pub fn sort_array_by<K, F>(&mut self, key_fn: F)
+    where
+        K: Ord,
+        F: Fn(&Value) -> K,
+    {
         match self {
-            Value::Number(n) => n.get_u128_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Array(array) => array.values.sort_by_key(|value| key_fn(value)),
+            _ => panic!("Unable to sort a type other than an array"),
         }
     }
 
-    fn get_i8_unsafe(&self) -> i8 {
+    fn numeric_elements(&self) -> Vec<&Number> {
         match self {
-            Value::Number(n) => n.get_i8_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+            Value::Array(array) => array
+                .into_iter()
+                .filter_map(|value| value.as_number())
+                .collect(),
+            _ => panic!("Unable to aggregate a type other than an array"),
         }
     }
 
-    fn get_i16_unsafe(&self) -> i16 {
-        match self {
-            Value::Number(n) => n.get_i16_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+    /// Sums the numbers of an array, skipping non-numeric elements. The result is an
+    /// integer unless a float (or an integer that overflows `i128`) is present.
+    pub fn numeric_sum(&self) -> Number {
+        let numbers = self.numeric_elements();
+
+        let mut integer_sum: Option<i128> = Some(0);
+        for number in numbers.iter() {
+            integer_sum = match (integer_sum, number.integer_as_i128()) {
+                (Some(sum), Some(value)) => sum.checked_add(value),
+                _ => None,
+            };
         }
-    }
 
-    fn get_i32_unsafe(&self) -> i32 {
-        match self {
-            Value::Number(n) => n.get_i32_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+        match integer_sum {
+            Some(sum) => match i64::try_from(sum) {
+                Ok(sum) => Number::from(sum),
+                Err(_) => Number::from(sum),
+            },
+            None => Number::from(
+                numbers
+                    .iter()
+                    .filter_map(|number| number.to_f64())
+                    .sum::<f64>(),
+            ),
         }
     }
 
-    fn get_i64_unsafe(&self) -> i64 {
-        match self {
-            Value::Number(n) => n.get_i64_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+    pub fn numeric_mean(&self) -> Option<Number> {
+        let numbers = self.numeric_elements();
+        if numbers.is_empty() {
+            return None;
         }
+
+        let sum: f64 = numbers.iter().filter_map(|number| number.to_f64()).sum();
+        Some(Number::from(sum / numbers.len() as f64))
     }
 
-    fn get_i128_unsafe(&self) -> i128 {
-        match self {
-            Value::Number(n) => n.get_i128_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
-        }
+    pub fn numeric_min(&self) -> Option<Number> {
+        self.numeric_elements()
+            .into_iter()
+            .min_by(|a, b| {
+                a.to_f64()
+                    .partial_cmp(&b.to_f64())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .cloned()
     }
 
-    fn get_f32_unsafe(&self) -> f32 {
-        match self {
-            Value::Number(n) => n.get_f32_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
-        }
+    pub fn numeric_max(&self) -> Option<Number> {
+        self.numeric_elements()
+            .into_iter()
+            .max_by(|a, b| {
+                a.to_f64()
+                    .partial_cmp(&b.to_f64())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .cloned()
     }
 
-    fn get_f64_unsafe(&self) -> f64 {
-        match self {
-            Value::Number(n) => n.get_f64_unsafe(),
-            _ => panic!("Unable to get a value other than a number"),
+    /// Counts the numbers of an array per bucket of width `bucket_size`, skipping
+    /// non-numeric elements. Returns an object keyed by each non-empty bucket's start
+    /// (`"0"`, `"5"`, ...) with the count as the value.
+    pub fn histogram(&self, bucket_size: f64) -> Value {
+        if !(bucket_size.is_finite() && bucket_size > 0.0) {
+            panic!("Unable to bucket with a size that is not a positive number");
         }
-    }
 
-    fn is_i8(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_i8(),
-            _ => false,
+        let mut buckets: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+        for number in self.numeric_elements() {
+            if let Some(value) = number.to_f64().filter(|value| value.is_finite()) {
+                let start = (value / bucket_size).floor() * bucket_size;
+                *buckets.entry(start.to_string()).or_insert(0) += 1;
+            }
         }
+        Value::from(buckets)
     }
 
-    fn is_i16(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_i16(),
+    /// Returns true if both values are dates or date-times and `self` comes first.
+    pub fn is_before(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::DateTime(a), Value::DateTime(b)) => a < b,
             _ => false,
         }
     }
 
-    fn is_i32(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_i32(),
+    /// Returns true if both values are dates or date-times and `self` comes later.
+    pub fn is_after(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::DateTime(a), Value::DateTime(b)) => a > b,
             _ => false,
         }
     }
 
-    fn is_i64(&self) -> bool {
+    pub fn insert<T, V>(&mut self, key: T, value: V) -> Option<Value>
+    where
+        T: ValueKeyBehavior,
+        V: ToValueBehavior,
+    {
         match self {
-            Value::Number(n) => n.is_i64(),
-            _ => false,
+            Value::Object(o) => o.insert(key, value.to_value()),
+            _ => panic!("Unable to insert values ​​into a type other than an object"),
         }
     }
 
-    fn is_i128(&self) -> bool {
+    /// Returns true if the value is an integer that can be narrowed to `i32` without loss.
+    pub fn fits_in_i32(&self) -> bool {
         match self {
-            Value::Number(n) => n.is_i128(),
+            Value::Number(n) => n.fits_in_i32(),
             _ => false,
         }
     }
 
-    fn is_u8(&self) -> bool {
+    /// Returns true if the value is an integer that can be narrowed to `u32` without loss.
+    pub fn fits_in_u32(&self) -> bool {
         match self {
-            Value::Number(n) => n.is_u8(),
+            Value::Number(n) => n.fits_in_u32(),
             _ => false,
         }
     }
 
-    fn is_u16(&self) -> bool {
+    /// Returns true if the value is a number that can be represented as an `f32` without
+    /// rounding.
+    pub fn fits_in_f32_exactly(&self) -> bool {
         match self {
-            Value::Number(n) => n.is_u16(),
+            Value::Number(n) => n.fits_in_f32_exactly(),
             _ => false,
         }
     }
+}
 
-    fn is_u32(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_u32(),
-            _ => false,
+pub(crate) fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+pub(crate) fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Concatenates arrays and strings, shallowly merges objects (entries from the right-hand
+/// side win) and adds numbers as `Add` does. Like the other collection helpers, it panics
+/// when the operands have different types or are not one of those; `+` returns
+/// `Value::Null` for the same operands instead.
+impl std::ops::AddAssign for Value {
+    fn add_assign(&mut self, other: Value) {
+        match (self, other) {
+            (Value::Array(array), Value::Array(other)) => array.values.extend(other.values),
+            (Value::String(string), Value::String(other)) => {
+                *string = string.concat(other.as_str())
+            }
+            (Value::Object(object), Value::Object(other)) => {
+                let entries: Vec<(ValueKey, Value)> = match other {
+                    Object::BTreeMap(map) => map.into_iter().collect(),
+                    Object::HashMap(map) => map.into_iter().collect(),
+                };
+                for (key, value) in entries {
+                    match object {
+                        Object::BTreeMap(map) => map.insert(key, value),
+                        Object::HashMap(map) => map.insert(key, value),
+                    };
+                }
+            }
+            (Value::Number(number), Value::Number(other)) => {
+                *number = number.combine_with(&other, ArithmeticOp::Add)
+            }
+            _ => panic!("Unable to concatenate values of different or non-collection types"),
         }
     }
+}
 
-    fn is_u64(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_u64(),
-            _ => false,
-        }
+/// Applies a binary operation to two numeric values, producing `Value::Null` when either
+/// operand is not a number. See `Number::combine_with` for the promotion rules.
+fn numeric_op(left: &Value, right: &Value, op: ArithmeticOp) -> Value {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => Value::Number(a.combine_with(b, op)),
+        _ => Value::Null,
     }
+}
 
-    fn is_u128(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_u128(),
-            _ => false,
+/// Adds two numbers, concatenates two arrays or strings, or merges two objects, like
+/// `+=`. Integers stay integers unless the sum overflows, in which case it is promoted to
+/// `f64`. Unlike `+=`, which panics, mismatched or unsupported operands produce
+/// `Value::Null`.
+impl std::ops::Add for Value {
+    type Output = Value;
+
+    fn add(mut self, other: Value) -> Value {
+        match (&self, &other) {
+            (Value::Array(_), Value::Array(_))
+            | (Value::String(_), Value::String(_))
+            | (Value::Object(_), Value::Object(_))
+            | (Value::Number(_), Value::Number(_)) => {
+                self += other;
+                self
+            }
+            _ => Value::Null,
         }
     }
+}
 
-    fn is_f32(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_f32(),
-            _ => false,
-        }
+/// Subtracts two numbers, following the same rules as `Add`.
+impl std::ops::Sub for Value {
+    type Output = Value;
+
+    fn sub(self, other: Value) -> Value {
+        numeric_op(&self, &other, ArithmeticOp::Sub)
     }
+}
 
-    fn is_f64(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_f64(),
-            _ => false,
-        }
+/// Multiplies two numbers, following the same rules as `Add`.
+impl std::ops::Mul for Value {
+    type Output = Value;
+
+    fn mul(self, other: Value) -> Value {
+        numeric_op(&self, &other, ArithmeticOp::Mul)
     }
+}
 
-    fn is_number(&self) -> bool {
-        match self {
-            Value::Number(_) => true,
-            _ => false,
+/// Divides two numbers. The quotient of two integers stays an integer only when it is
+/// exact (`6 / 3` is `2`, `7 / 2` is `3.5`). Division by zero produces `Value::Null`.
+impl std::ops::Div for Value {
+    type Output = Value;
+
+    fn div(self, other: Value) -> Value {
+        match &other {
+            Value::Number(divisor) if divisor.is_zero() => Value::Null,
+            _ => numeric_op(&self, &other, ArithmeticOp::Div),
         }
     }
+}
 
-    fn is_integer(&self) -> bool {
+impl NumberBehavior for Value {
+    fn set_u8(&mut self, value: u8) {
         match self {
-            Value::Number(n) => n.is_integer(),
-            _ => false,
+            Value::Number(n) => n.set_u8(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn is_float(&self) -> bool {
+    fn set_u16(&mut self, value: u16) {
         match self {
-            Value::Number(n) => n.is_float(),
-            _ => false,
+            Value::Number(n) => n.set_u16(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn is_signed(&self) -> bool {
+    fn set_u32(&mut self, value: u32) {
         match self {
-            Value::Number(n) => n.is_signed(),
-            _ => false,
+            Value::Number(n) => n.set_u32(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn is_unsigned(&self) -> bool {
+    fn set_u64(&mut self, value: u64) {
         match self {
-            Value::Number(n) => n.is_unsigned(),
-            _ => false,
+            Value::Number(n) => n.set_u64(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn is_zero(&self) -> bool {
+    fn set_u128(&mut self, value: u128) {
         match self {
-            Value::Number(n) => n.is_zero(),
-            _ => false,
+            Value::Number(n) => n.set_u128(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn is_positive(&self) -> bool {
+    fn set_i8(&mut self, value: i8) {
         match self {
-            Value::Number(n) => n.is_positive(),
-            _ => false,
+            Value::Number(n) => n.set_i8(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn is_negative(&self) -> bool {
+    fn set_i16(&mut self, value: i16) {
         match self {
-            Value::Number(n) => n.is_negative(),
-            _ => false,
+            Value::Number(n) => n.set_i16(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn number_type(&self) -> NumberType {
+    fn set_i32(&mut self, value: i32) {
         match self {
-            Value::Number(n) => n.number_type(),
-            _ => NumberType::Unknown,
+            Value::Number(n) => n.set_i32(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn to_f64(&self) -> Option<f64> {
+    fn set_i64(&mut self, value: i64) {
         match self {
-            Value::Number(n) => n.to_f64(),
-            _ => None,
+            Value::Number(n) => n.set_i64(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn to_i64(&self) -> Option<i64> {
+    fn set_i128(&mut self, value: i128) {
         match self {
-            Value::Number(n) => n.to_i64(),
-            _ => None,
+            Value::Number(n) => n.set_i128(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn to_u64(&self) -> Option<u64> {
+    fn set_f32(&mut self, value: f32) {
         match self {
-            Value::Number(n) => n.to_u64(),
-            _ => None,
+            Value::Number(n) => n.set_f32(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
-}
 
-impl ObjectBehavior for Value {
-    fn remove<T>(&mut self, key: &T) -> Option<Value>
-    where
-        T: ValueKeyBehavior,
-    {
+    fn set_f64(&mut self, value: f64) {
         match self {
-            Value::Object(o) => o.remove(key),
-            _ => panic!("Unable to remove a value other than an object"),
+            Value::Number(n) => n.set_f64(value),
+            _ => panic!("Unable to set a value other than a number"),
         }
     }
 
-    fn contains_key<T>(&self, key: &T) -> bool
-    where
-        T: ValueKeyBehavior,
-    {
+    fn get_u8(&self) -> Option<u8> {
         match self {
-            Value::Object(o) => o.contains_key(key),
-            _ => panic!("Unable to remove a value other than an object"),
+            Value::Number(n) => n.get_u8(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn keys(&self) -> Vec<&ValueKey> {
+    fn get_u16(&self) -> Option<u16> {
         match self {
-            Value::Object(o) => o.keys(),
-            _ => panic!("Unable to remove a value other than an object"),
+            Value::Number(n) => n.get_u16(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn values(&self) -> Vec<&Value> {
+    fn get_u32(&self) -> Option<u32> {
         match self {
-            Value::Object(o) => o.values(),
-            _ => panic!("Unable to remove a value other than an object"),
+            Value::Number(n) => n.get_u32(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
-}
 
-impl ArrayBehavior for Value {
-    fn pop(&mut self) -> Option<Value> {
+    fn get_u64(&self) -> Option<u64> {
         match self {
-            Value::Array(array) => array.pop(),
-            _ => panic!("Unable to pop a value other than an array"),
+            Value::Number(n) => n.get_u64(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
-}
 
-impl DateTimeBehavior for Value {
-    fn as_date(&self) -> Option<&chrono::NaiveDate> {
+    fn get_u128(&self) -> Option<u128> {
         match self {
-            Value::DateTime(datetime) => datetime.as_date(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_u128(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn as_time(&self) -> Option<&chrono::NaiveTime> {
+    fn get_i8(&self) -> Option<i8> {
         match self {
-            Value::DateTime(datetime) => datetime.as_time(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i8(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn as_date_time(&self) -> Option<&chrono::DateTime<chrono::Utc>> {
+    fn get_i16(&self) -> Option<i16> {
         match self {
-            Value::DateTime(datetime) => datetime.as_date_time(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i16(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn year(&self) -> Option<i32> {
+    fn get_i32(&self) -> Option<i32> {
         match self {
-            Value::DateTime(datetime) => datetime.year(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i32(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn month(&self) -> Option<u32> {
+    fn get_i64(&self) -> Option<i64> {
         match self {
-            Value::DateTime(datetime) => datetime.month(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i64(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn day(&self) -> Option<u32> {
+    fn get_i128(&self) -> Option<i128> {
         match self {
-            Value::DateTime(datetime) => datetime.day(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i128(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn hour(&self) -> Option<u32> {
+    fn get_f32(&self) -> Option<f32> {
         match self {
-            Value::DateTime(datetime) => datetime.hour(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_f32(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn minute(&self) -> Option<u32> {
+    fn get_f64(&self) -> Option<f64> {
         match self {
-            Value::DateTime(datetime) => datetime.minute(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_f64(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn second(&self) -> Option<u32> {
+    fn get_u8_unsafe(&self) -> u8 {
         match self {
-            Value::DateTime(datetime) => datetime.second(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_u8_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn timestamp(&self) -> Option<i64> {
+    fn get_u16_unsafe(&self) -> u16 {
         match self {
-            Value::DateTime(datetime) => datetime.timestamp(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_u16_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn timezone(&self) -> Option<chrono::Utc> {
+    fn get_u32_unsafe(&self) -> u32 {
         match self {
-            Value::DateTime(datetime) => datetime.timezone(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_u32_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn to_iso8601(&self) -> String {
+    fn get_u64_unsafe(&self) -> u64 {
         match self {
-            Value::DateTime(datetime) => datetime.to_iso8601(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_u64_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn to_rfc3339(&self) -> String {
+    fn get_u128_unsafe(&self) -> u128 {
         match self {
-            Value::DateTime(datetime) => datetime.to_rfc3339(),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_u128_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn add_duration(&self, duration: chrono::Duration) -> Option<Self>
-    where
-        Self: Sized,
-    {
+    fn get_i8_unsafe(&self) -> i8 {
         match self {
-            Value::DateTime(datetime) => match datetime.add_duration(duration) {
-                Some(datetime) => Some(datetime.to_value()),
-                None => None,
-            },
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i8_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn subtract_duration(&self, duration: chrono::Duration) -> Option<Self>
-    where
-        Self: Sized,
-    {
+    fn get_i16_unsafe(&self) -> i16 {
         match self {
-            Value::DateTime(datetime) => match datetime.subtract_duration(duration) {
-                Some(datetime) => Some(datetime.to_value()),
-                None => None,
-            },
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i16_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn duration_between(&self, other: &Self) -> Option<chrono::Duration> {
+    fn get_i32_unsafe(&self) -> i32 {
         match self {
-            Value::DateTime(datetime) => datetime.duration_between(&DateTime::from(other.clone())),
-            _ => panic!("Unable to get a date from a value other than a datetime"),
+            Value::Number(n) => n.get_i32_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn from_ymd_opt(year: i32, month: u32, day: u32) -> Self {
-        DateTime::from_ymd_opt(year, month, day).to_value()
+    fn get_i64_unsafe(&self) -> i64 {
+        match self {
+            Value::Number(n) => n.get_i64_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
+        }
     }
 
-    fn with_ymd_and_hms(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> Self {
-        DateTime::with_ymd_and_hms(year, month, day, hour, min, sec).to_value()
+    fn get_i128_unsafe(&self) -> i128 {
+        match self {
+            Value::Number(n) => n.get_i128_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
+        }
     }
 
-    fn now() -> Self {
-        DateTime::now().to_value()
+    fn get_f32_unsafe(&self) -> f32 {
+        match self {
+            Value::Number(n) => n.get_f32_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
+        }
     }
-}
 
-impl StringBehavior for Value {
-    fn as_bytes(&self) -> &[u8] {
+    fn get_f64_unsafe(&self) -> f64 {
         match self {
-            Value::String(string) => string.as_bytes(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.get_f64_unsafe(),
+            _ => panic!("Unable to get a value other than a number"),
         }
     }
 
-    fn as_str(&self) -> &str {
+    fn is_i8(&self) -> bool {
         match self {
-            Value::String(string) => string.as_str(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_i8(),
+            _ => false,
         }
     }
 
-    fn as_string(&self) -> String {
+    fn is_i16(&self) -> bool {
         match self {
-            Value::String(string) => string.as_string(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_i16(),
+            _ => false,
         }
     }
 
-    #[cfg(feature = "cstring")]
-    fn extract(&self) -> CString {
+    fn is_i32(&self) -> bool {
         match self {
-            Value::String(string) => string.extract(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_i32(),
+            _ => false,
         }
     }
 
-    #[cfg(not(feature = "cstring"))]
-    fn extract(&self) -> String {
+    fn is_i64(&self) -> bool {
         match self {
-            Value::String(string) => string.extract(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_i64(),
+            _ => false,
         }
     }
 
-    fn to_uppercase(&self) -> Self {
+    fn is_i128(&self) -> bool {
         match self {
-            Value::String(string) => string.to_uppercase().to_value(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_i128(),
+            _ => false,
         }
     }
 
-    fn to_lowercase(&self) -> Self {
+    fn is_u8(&self) -> bool {
         match self {
-            Value::String(string) => string.to_lowercase().to_value(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_u8(),
+            _ => false,
         }
     }
 
-    fn trim(&self) -> Self {
+    fn is_u16(&self) -> bool {
         match self {
-            Value::String(string) => string.trim().to_value(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_u16(),
+            _ => false,
         }
     }
 
-    fn replace(&self, from: &str, to: &str) -> Self {
+    fn is_u32(&self) -> bool {
         match self {
-            Value::String(string) => string.replace(from, to).to_value(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_u32(),
+            _ => false,
         }
     }
 
-    fn concat<T: AsRef<str>>(&self, other: T) -> Self {
+    fn is_u64(&self) -> bool {
         match self {
-            Value::String(string) => string.concat(other).to_value(),
-            _ => panic!("Unable to get a string from a value other than a string"),
+            Value::Number(n) => n.is_u64(),
+            _ => false,
         }
     }
 
-    fn from_utf8(value: Vec<u8>) -> Self {
-        StringB::from_utf8(value).to_value()
+    fn is_u128(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_u128(),
+            _ => false,
+        }
     }
-}
 
-impl From<()> for Value {
-    fn from(_: ()) -> Self {
-        Value::Null
+    fn is_f32(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_f32(),
+            _ => false,
+        }
     }
-}
 
-impl<T> From<T> for Value
-where
-    T: ToValueBehavior + PrimitiveType,
-{
-    fn from(value: T) -> Self {
-        value.to_value()
+    fn is_f64(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_f64(),
+            _ => false,
+        }
     }
-}
 
-impl<K, V> From<Vec<(K, V)>> for Value
-where
-    K: ValueKeyBehavior,
-    V: ToValueBehavior + PrimitiveType,
-{
-    fn from(value: Vec<(K, V)>) -> Self {
-        let mut object = Object::default();
-        for (key, value) in value {
-            object.insert(key, value.to_value());
+    fn is_number(&self) -> bool {
+        match self {
+            Value::Number(_) => true,
+            _ => false,
         }
-        Value::Object(object)
     }
-}
 
-impl<K> From<Vec<(K, Value)>> for Value
-where
-    K: ValueKeyBehavior,
-{
-    fn from(value: Vec<(K, Value)>) -> Self {
-        let mut object = Object::default();
-        for (key, value) in value {
-            object.insert(key, value);
+    fn is_integer(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_integer(),
+            _ => false,
         }
-        Value::Object(object)
     }
-}
-
-//TODO: implement [(K, V)] and [(K, Value)]
 
-#[cfg(test)]
-mod tests {
-    use crate::prelude::*;
-    use std::collections::HashMap;
+    fn is_float(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_float(),
+            _ => false,
+        }
+    }
 
-    #[test]
-    fn test_value_number_behavior() {
-        let value = Value::from(3.14);
-        assert_eq!(value.get_f64_unsafe(), 3.14);
+    fn is_signed(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_signed(),
+            _ => false,
+        }
+    }
 
-        let value2 = Value::from(42u32);
-        assert_eq!(value2.to_u64(), Some(42));
+    fn is_unsigned(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_unsigned(),
+            _ => false,
+        }
     }
 
-    #[test]
+    fn is_zero(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_zero(),
+            _ => false,
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_positive(),
+            _ => false,
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_negative(),
+            _ => false,
+        }
+    }
+
+    fn number_type(&self) -> NumberType {
+        match self {
+            Value::Number(n) => n.number_type(),
+            _ => NumberType::Unknown,
+        }
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => n.to_f64(),
+            _ => None,
+        }
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(n) => n.to_i64(),
+            _ => None,
+        }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.to_u64(),
+            _ => None,
+        }
+    }
+}
+
+impl ObjectBehavior for Value {
+    fn remove<T>(&mut self, key: &T) -> Option<Value>
+    where
+        T: ValueKeyBehavior,
+    {
+        match self {
+            Value::Object(o) => o.remove(key),
+            _ => panic!("Unable to remove a value other than an object"),
+        }
+    }
+
+    fn contains_key<T>(&self, key: &T) -> bool
+    where
+        T: ValueKeyBehavior,
+    {
+        match self {
+            Value::Object(o) => o.contains_key(key),
+            _ => panic!("Unable to remove a value other than an object"),
+        }
+    }
+
+    fn keys(&self) -> Vec<&ValueKey> {
+        match self {
+            Value::Object(o) => o.keys(),
+            _ => panic!("Unable to remove a value other than an object"),
+        }
+    }
+
+    fn values(&self) -> Vec<&Value> {
+        match self {
+            Value::Object(o) => o.values(),
+            _ => panic!("Unable to remove a value other than an object"),
+        }
+    }
+}
+
+impl ArrayBehavior for Value {
+    fn pop(&mut self) -> Option<Value> {
+        match self {
+            Value::Array(array) => array.pop(),
+            _ => panic!("Unable to pop a value other than an array"),
+        }
+    }
+}
+
+impl DateTimeBehavior for Value {
+    fn as_date(&self) -> Option<&chrono::NaiveDate> {
+        match self {
+            Value::DateTime(datetime) => datetime.as_date(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn as_time(&self) -> Option<&chrono::NaiveTime> {
+        match self {
+            Value::DateTime(datetime) => datetime.as_time(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn as_date_time(&self) -> Option<&chrono::DateTime<chrono::Utc>> {
+        match self {
+            Value::DateTime(datetime) => datetime.as_date_time(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn year(&self) -> Option<i32> {
+        match self {
+            Value::DateTime(datetime) => datetime.year(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn month(&self) -> Option<u32> {
+        match self {
+            Value::DateTime(datetime) => datetime.month(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn day(&self) -> Option<u32> {
+        match self {
+            Value::DateTime(datetime) => datetime.day(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn hour(&self) -> Option<u32> {
+        match self {
+            Value::DateTime(datetime) => datetime.hour(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn minute(&self) -> Option<u32> {
+        match self {
+            Value::DateTime(datetime) => datetime.minute(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn second(&self) -> Option<u32> {
+        match self {
+            Value::DateTime(datetime) => datetime.second(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn timestamp(&self) -> Option<i64> {
+        match self {
+            Value::DateTime(datetime) => datetime.timestamp(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn timezone(&self) -> Option<chrono::Utc> {
+        match self {
+            Value::DateTime(datetime) => datetime.timezone(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn to_iso8601(&self) -> String {
+        match self {
+            Value::DateTime(datetime) => datetime.to_iso8601(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn to_rfc3339(&self) -> String {
+        match self {
+            Value::DateTime(datetime) => datetime.to_rfc3339(),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn add_duration(&self, duration: chrono::Duration) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        match self {
+            Value::DateTime(datetime) => match datetime.add_duration(duration) {
+                Some(datetime) => Some(datetime.to_value()),
+                None => None,
+            },
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn subtract_duration(&self, duration: chrono::Duration) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        match self {
+            Value::DateTime(datetime) => match datetime.subtract_duration(duration) {
+                Some(datetime) => Some(datetime.to_value()),
+                None => None,
+            },
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn duration_between(&self, other: &Self) -> Option<chrono::Duration> {
+        match self {
+            Value::DateTime(datetime) => datetime.duration_between(&DateTime::from(other.clone())),
+            _ => panic!("Unable to get a date from a value other than a datetime"),
+        }
+    }
+
+    fn from_ymd_opt(year: i32, month: u32, day: u32) -> Self {
+        DateTime::from_ymd_opt(year, month, day).to_value()
+    }
+
+    fn with_ymd_and_hms(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> Self {
+        DateTime::with_ymd_and_hms(year, month, day, hour, min, sec).to_value()
+    }
+
+    fn now() -> Self {
+        DateTime::now().to_value()
+    }
+}
+
+impl StringBehavior for Value {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Value::String(string) => string.as_bytes(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Value::String(string) => string.as_str(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn as_string(&self) -> String {
+        match self {
+            Value::String(string) => string.as_string(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    #[cfg(feature = "cstring")]
+    fn extract(&self) -> CString {
+        match self {
+            Value::String(string) => string.extract(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    #[cfg(not(feature = "cstring"))]
+    fn extract(&self) -> String {
+        match self {
+            Value::String(string) => string.extract(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn to_uppercase(&self) -> Self {
+        match self {
+            Value::String(string) => string.to_uppercase().to_value(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn to_lowercase(&self) -> Self {
+        match self {
+            Value::String(string) => string.to_lowercase().to_value(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn trim(&self) -> Self {
+        match self {
+            Value::String(string) => string.trim().to_value(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn replace(&self, from: &str, to: &str) -> Self {
+        match self {
+            Value::String(string) => string.replace(from, to).to_value(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn concat<T: AsRef<str>>(&self, other: T) -> Self {
+        match self {
+            Value::String(string) => string.concat(other).to_value(),
+            _ => panic!("Unable to get a string from a value other than a string"),
+        }
+    }
+
+    fn from_utf8(value: Vec<u8>) -> Self {
+        StringB::from_utf8(value).to_value()
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Self {
+        Value::Null
+    }
+}
+
+impl<T> From<T> for Value
+where
+    T: ToValueBehavior + PrimitiveType,
+{
+    fn from(value: T) -> Self {
+        value.to_value()
+    }
+}
+
+impl<K, V> From<Vec<(K, V)>> for Value
+where
+    K: ValueKeyBehavior,
+    V: ToValueBehavior + PrimitiveType,
+{
+    fn from(value: Vec<(K, V)>) -> Self {
+        let mut object = Object::default();
+        for (key, value) in value {
+            object.insert(key, value.to_value());
+        }
+        Value::Object(object)
+    }
+}
+
+impl<K> From<Vec<(K, Value)>> for Value
+where
+    K: ValueKeyBehavior,
+{
+    fn from(value: Vec<(K, Value)>) -> Self {
+        let mut object = Object::default();
+        for (key, value) in value {
+            object.insert(key, value);
+        }
+        Value::Object(object)
+    }
+}
+
+//TODO: implement [(K, V)] and [(K, Value)]
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_value_number_behavior() {
+        let value = Value::from(3.14);
+        assert_eq!(value.get_f64_unsafe(), 3.14);
+
+        let value2 = Value::from(42u32);
+        assert_eq!(value2.to_u64(), Some(42));
+    }
+
+    #[test]
     fn test_value_object_behavior() {
         let mut value = Value::from(HashMap::from_iter(vec![("1", 3.14.to_value())].into_iter()));
         value.insert("2", 4.13);
 
-        if let Some(item) = value.get_mut("1") {
-            *item = 1.43.to_value();
+        if let Some(item) = value.get_mut("1") {
+            *item = 1.43.to_value();
+        }
+
+        assert_eq!(value.get("1").unwrap(), &1.43.to_value());
+    }
+
+    #[test]
+    fn test_value_object_with_capacity() {
+        // Pre-sizing avoids rehashing while keys are inserted; the result matches
+        // an object built incrementally.
+        let key_count = 1000;
+        let mut presized = Value::object_with_capacity(key_count);
+        let mut grown = Value::from(HashMap::<String, Value>::new());
+        for i in 0..key_count {
+            presized.insert(format!("key_{}", i), format!("value_{}", i));
+            grown.insert(format!("key_{}", i), format!("value_{}", i));
+        }
+
+        assert_eq!(presized.object_values().len(), key_count);
+        for i in 0..key_count {
+            let key = format!("key_{}", i);
+            assert_eq!(presized.get(key.as_str()), grown.get(key.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_value_array_behavior() {
+        let mut value = Value::from(vec![1, 2, 3]);
+        value.push(4);
+
+        if let Some(item) = value.get_mut("1") {
+            *item = 1.43.to_value();
+        }
+
+        assert_eq!(value.get("1").unwrap(), &1.43.to_value());
+    }
+
+    #[test]
+    fn test_value_array_dedup() {
+        let mut value = Value::from(vec![1, 2, 2, 1, 3]);
+        value.array_dedup();
+        assert_eq!(value, Value::from(vec![1, 2, 3]));
+
+        let mut value = Value::from(vec![
+            Value::from(vec![("id", 1)]),
+            Value::from(vec![("id", 1)]),
+            Value::from(vec![("id", 2)]),
+        ]);
+        value.array_dedup_by_key("id");
+        assert_eq!(value.len(), 2);
+    }
+
+    #[test]
+    fn test_value_array_splice() {
+        let mut value = Value::from(vec![0, 1, 2, 3, 4]);
+        let removed = value.array_splice(1..3, vec![Value::from("a"), Value::from("b")]);
+
+        assert_eq!(removed, vec![Value::from(1), Value::from(2)]);
+        assert_eq!(
+            value,
+            Value::from(vec![
+                Value::from(0),
+                Value::from("a"),
+                Value::from("b"),
+                Value::from(3),
+                Value::from(4)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_value_filter_map_array() {
+        let value = Value::from(vec![
+            Value::from(1),
+            Value::from(2),
+            Value::from("3"),
+            Value::from(4),
+            Value::Null,
+        ]);
+
+        let scaled_evens = value.filter_map_array(|item| match item.as_number() {
+            Some(number) => number
+                .to_i64()
+                .filter(|n| n % 2 == 0)
+                .map(|n| Value::from(n * 10)),
+            None => None,
+        });
+        assert_eq!(scaled_evens, Value::from(vec![20i64, 40]));
+    }
+
+    #[test]
+    fn test_value_partition_array() {
+        let value = Value::from(vec![1, 2, 3, 4, 5, 6]);
+
+        let (evens, odds) = value.partition_array(|item| {
+            item.as_number()
+                .and_then(|number| number.to_i64())
+                .is_some_and(|n| n % 2 == 0)
+        });
+        assert_eq!(evens, Value::from(vec![2, 4, 6]));
+        assert_eq!(odds, Value::from(vec![1, 3, 5]));
+
+        let (all, none) = value.partition_array(|_| true);
+        assert_eq!(all, value);
+        assert_eq!(none, Value::from(Vec::<Value>::new()));
+    }
+
+    #[test]
+    fn test_value_sort_array_by() {
+        let mut value = Value::from(vec![
+            Value::from(vec![("name", "charlotte"), ("id", "1")]),
+            Value::from(vec![("name", "bob"), ("id", "2")]),
+            Value::from(vec![("name", "eve"), ("id", "3")]),
+            Value::from(vec![("name", "alice"), ("id", "4")]),
+        ]);
+
+        value.sort_array_by(|item| item.get("name").map_or(0, |name| name.as_str().len()));
+
+        let ids = match &value {
+            Value::Array(array) => array
+                .into_iter()
+                .map(|item| item.get("id").unwrap().as_str().to_string())
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+        // "bob" and "eve" tie on length and keep their original order.
+        assert_eq!(ids, vec!["2", "3", "4", "1"]);
+    }
+
+    #[test]
+    fn test_value_array_front() {
+        let mut queue = Value::from(Vec::<Value>::new());
+        queue.push("b");
+        queue.array_push_front("a");
+        queue.push("c");
+        assert_eq!(queue, Value::from(vec!["a", "b", "c"]));
+
+        assert_eq!(queue.array_pop_front(), Some("a".to_value()));
+        queue.array_push_front("z");
+        assert_eq!(queue, Value::from(vec!["z", "b", "c"]));
+    }
+
+    #[test]
+    fn test_value_array_reverse_and_rotate() {
+        let mut value = Value::from(vec![1, 2, 3, 4]);
+
+        value.array_reverse();
+        assert_eq!(value, Value::from(vec![4, 3, 2, 1]));
+
+        value.array_reverse();
+        value.array_rotate_left(1);
+        assert_eq!(value, Value::from(vec![2, 3, 4, 1]));
+
+        value.array_rotate_right(1);
+        assert_eq!(value, Value::from(vec![1, 2, 3, 4]));
+
+        value.array_rotate_right(5);
+        assert_eq!(value, Value::from(vec![4, 1, 2, 3]));
+
+        let mut empty = Value::from(Vec::<Value>::new());
+        empty.array_rotate_left(3);
+        let mut scalar = Value::from("abc");
+        scalar.array_reverse();
+        assert_eq!(scalar, Value::from("abc"));
+    }
+
+    #[test]
+    fn test_value_array_windows() {
+        let value = Value::from(vec![1, 2, 3, 4]);
+
+        assert_eq!(
+            value.array_windows(2),
+            vec![
+                Value::from(vec![1, 2]),
+                Value::from(vec![2, 3]),
+                Value::from(vec![3, 4]),
+            ]
+        );
+        assert!(value.array_windows(5).is_empty());
+        assert!(value.array_windows(0).is_empty());
+        assert!(Value::from("1234").array_windows(2).is_empty());
+    }
+
+    #[test]
+    fn test_value_array_index_of() {
+        let value = Value::from(vec![
+            Value::from(1),
+            Value::from("a"),
+            Value::from(vec![("id", 2)]),
+            Value::Null,
+            Value::from("a"),
+        ]);
+
+        assert_eq!(value.array_index_of(&Value::from("a")), Some(1));
+        assert_eq!(value.array_last_index_of(&Value::from("a")), Some(4));
+        assert_eq!(value.array_index_of(&Value::from(vec![("id", 2)])), Some(2));
+        assert_eq!(value.array_index_of(&Value::Null), Some(3));
+        assert_eq!(value.array_index_of(&Value::from("b")), None);
+        assert_eq!(value.array_last_index_of(&Value::from(2)), None);
+        assert_eq!(Value::from("a").array_index_of(&Value::from("a")), None);
+    }
+
+    #[test]
+    fn test_value_array_fold() {
+        let value = Value::from(vec![1, 2, 3, 4]);
+        let total = value.array_fold(0.0, |acc, item| acc + item.to_f64().unwrap_or(0.0));
+        assert_eq!(total, 10.0);
+
+        let words = Value::from(vec!["a", "b", "c"]);
+        let joined = words.array_fold(String::new(), |acc, item| acc + item.as_str());
+        assert_eq!(joined, "abc");
+
+        let empty = Value::from(Vec::<i32>::new());
+        assert_eq!(empty.array_fold(42, |acc, _| acc + 1), 42);
+    }
+
+    #[test]
+    fn test_value_array_reduce() {
+        let value = Value::from(vec![3, 7, 5]);
+        let max = value.array_reduce(|acc, item| if item > &acc { item.clone() } else { acc });
+        assert_eq!(max, Some(7.to_value()));
+
+        let empty = Value::from(Vec::<i32>::new());
+        assert_eq!(empty.array_reduce(|acc, _| acc), None);
+    }
+
+    #[test]
+    fn test_value_numeric_aggregates() {
+        let value = Value::from(vec![
+            Value::from(1),
+            Value::from("x"),
+            Value::from(2.5),
+            Value::from(true),
+            Value::from(3),
+        ]);
+
+        assert_eq!(value.numeric_sum(), Number::from(6.5));
+        assert_eq!(value.numeric_mean(), Some(Number::from(6.5 / 3.0)));
+        assert_eq!(value.numeric_min(), Some(Number::from(1)));
+        assert_eq!(value.numeric_max(), Some(Number::from(3)));
+
+        let integers = Value::from(vec![1, 2, 3]);
+        assert_eq!(integers.numeric_sum(), Number::from(6i64));
+
+        let empty = Value::from(vec!["a", "b"]);
+        assert_eq!(empty.numeric_sum(), Number::from(0i64));
+        assert_eq!(empty.numeric_mean(), None);
+        assert_eq!(empty.numeric_max(), None);
+    }
+
+    #[test]
+    fn test_value_histogram() {
+        let value = Value::from(vec![1, 2, 2, 3, 10]);
+        let histogram = value.histogram(5.0);
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram.get("0"), Some(&Value::from(4u64)));
+        assert_eq!(histogram.get("10"), Some(&Value::from(1u64)));
+        assert_eq!(histogram.get("5"), None);
+
+        let mixed = Value::from(vec![Value::from(-0.5), Value::from("x"), Value::from(0.25)]);
+        let histogram = mixed.histogram(0.5);
+        assert_eq!(histogram.get("-0.5"), Some(&Value::from(1u64)));
+        assert_eq!(histogram.get("0"), Some(&Value::from(1u64)));
+    }
+
+    #[test]
+    fn test_value_flatten_array() {
+        let value = Value::from(vec![
+            Value::from(vec![1, 2]),
+            Value::from(vec![3.to_value(), Value::from(vec![4])]),
+        ]);
+
+        assert_eq!(
+            value.flatten_array(1),
+            Value::from(vec![
+                1.to_value(),
+                2.to_value(),
+                3.to_value(),
+                Value::from(vec![4])
+            ])
+        );
+        assert_eq!(value.flatten_array(2), Value::from(vec![1, 2, 3, 4]));
+        assert_eq!(value.flatten_array(0), value);
+    }
+
+    #[test]
+    fn test_value_rename_keys() {
+        let to_camel_case = |key: &str| {
+            let mut out = String::new();
+            let mut upper = false;
+            for c in key.chars() {
+                if c == '_' {
+                    upper = true;
+                } else if upper {
+                    out.extend(c.to_uppercase());
+                    upper = false;
+                } else {
+                    out.push(c);
+                }
+            }
+            out
+        };
+
+        let mut value = Value::from(vec![
+            ("user_name", Value::from("alice")),
+            (
+                "home_address",
+                Value::from(vec![("zip_code", "123"), ("street_name", "main")]),
+            ),
+            (
+                "phone_numbers",
+                Value::from(vec![Value::from(vec![("is_primary", true)])]),
+            ),
+        ]);
+
+        let collisions = value.rename_keys(to_camel_case);
+        assert!(collisions.is_empty());
+
+        let expected = Value::from(vec![
+            ("userName", Value::from("alice")),
+            (
+                "homeAddress",
+                Value::from(vec![("zipCode", "123"), ("streetName", "main")]),
+            ),
+            (
+                "phoneNumbers",
+                Value::from(vec![Value::from(vec![("isPrimary", true)])]),
+            ),
+        ]);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_value_rename_keys_collision() {
+        let mut value = Value::from(vec![("a", 1), ("b", 2)]);
+        let collisions = value.rename_keys(|_| "same".to_string());
+
+        assert_eq!(collisions, vec!["same".to_string()]);
+        assert_eq!(value.len(), 1);
+    }
+
+    #[test]
+    fn test_value_pointer() {
+        let mut value = Value::from(vec![
+            (
+                "users",
+                Value::from(vec![Value::from(vec![("name", "alice")])]),
+            ),
+            ("a/b", Value::from(1)),
+            ("m~n", Value::from(2)),
+        ]);
+
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/users/0/name"), Some(&"alice".to_value()));
+        assert_eq!(value.pointer("/a~1b"), Some(&1.to_value()));
+        assert_eq!(value.pointer("/m~0n"), Some(&2.to_value()));
+        assert_eq!(value.pointer("/users/1"), None);
+        assert_eq!(value.pointer("users"), None);
+
+        if let Some(name) = value.pointer_mut("/users/0/name") {
+            *name = "bob".to_value();
         }
+        assert_eq!(value.pointer("/users/0/name"), Some(&"bob".to_value()));
+    }
 
-        assert_eq!(value.get("1").unwrap(), &1.43.to_value());
+    #[test]
+    fn test_value_get_path_mut() {
+        use crate::json;
+
+        let mut value = json!({"server": {"ports": [80, 443], "tls": {"enabled": false}}});
+
+        if let Some(enabled) = value.get_path_mut("server.tls.enabled") {
+            *enabled = Value::Boolean(true);
+        }
+        *value.get_path_mut("server.ports.1").unwrap() = Value::from(8443);
+
+        assert_eq!(
+            value.get_path("server.tls.enabled"),
+            Some(&Value::Boolean(true))
+        );
+        assert_eq!(value.pointer("/server/ports/1"), Some(&8443.to_value()));
+        assert_eq!(value.get_path(""), Some(&value));
+
+        let before = value.clone();
+        assert!(value.get_path_mut("server.tls.cert").is_none());
+        assert!(value.get_path_mut("server.ports.5").is_none());
+        assert!(value.get_path_mut("server.ports.0.x").is_none());
+        assert_eq!(value, before);
     }
 
     #[test]
-    fn test_value_array_behavior() {
-        let mut value = Value::from(vec![1, 2, 3]);
-        value.push(4);
+    fn test_value_pointer_or_create() {
+        let mut value = Value::from(Object::default());
 
-        if let Some(item) = value.get_mut("1") {
-            *item = 1.43.to_value();
+        let target = value.pointer_or_create("/a/b/c");
+        assert_eq!(target, &Value::Null);
+        *target = "deep".to_value();
+
+        assert!(value.pointer("/a").unwrap().is_object());
+        assert!(value.pointer("/a/b").unwrap().is_object());
+        assert_eq!(value.pointer("/a/b/c"), Some(&"deep".to_value()));
+
+        *value.pointer_or_create("/a/b/d") = 1.to_value();
+        assert_eq!(value.pointer("/a/b/c"), Some(&"deep".to_value()));
+        assert_eq!(value.pointer("/a/b/d"), Some(&1.to_value()));
+
+        *value.pointer_or_create("/list/2/name") = "third".to_value();
+        assert_eq!(value.pointer("/list/0"), Some(&Value::Null));
+        assert_eq!(value.pointer("/list/2/name"), Some(&"third".to_value()));
+        assert_eq!(value.pointer("/list").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_value_arithmetic() {
+        assert_eq!(Value::from(2) + Value::from(3), Value::from(5i64));
+        assert_eq!(Value::from(2) - Value::from(5), Value::from(-3i64));
+        assert_eq!(Value::from(4) * Value::from(2.5), Value::from(10.0));
+        assert_eq!(Value::from(6) / Value::from(3), Value::from(2i64));
+        assert_eq!(Value::from(7) / Value::from(2), Value::from(3.5));
+        assert_eq!(
+            Value::from(i64::MAX) + Value::from(1),
+            Value::from(i64::MAX as i128 + 1)
+        );
+        assert_eq!(Value::from("ab") + Value::from("cd"), Value::from("abcd"));
+    }
+
+    #[test]
+    fn test_value_arithmetic_non_numeric() {
+        assert_eq!(Value::from(1) + Value::from("1"), Value::Null);
+        assert_eq!(Value::from("a") - Value::from("b"), Value::Null);
+        assert_eq!(Value::Null * Value::from(2), Value::Null);
+        assert_eq!(Value::from(1) / Value::from(0), Value::Null);
+        assert_eq!(Value::from(1.5) / Value::from(0.0), Value::Null);
+    }
+
+    #[test]
+    fn test_value_try_for_each() {
+        use std::ops::ControlFlow;
+
+        let value = Value::from(vec![
+            Value::from(vec![("c", 4)]),
+            Value::from(vec![1, 2, 3]),
+        ]);
+
+        let mut visited = Vec::new();
+        let found = value.try_for_each(|path, node| {
+            visited.push(path.to_string());
+            if node == &2.to_value() {
+                ControlFlow::Break(path.to_string())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(found, ControlFlow::Break("/1/1".to_string()));
+        assert_eq!(visited, vec!["", "/0", "/0/c", "/1", "/1/0", "/1/1"]);
+
+        let mut count = 0;
+        let result: ControlFlow<()> = value.try_for_each(|_, _| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        assert_eq!(result, ControlFlow::Continue(()));
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn test_value_is_before_and_after() {
+        let start = Value::from(DateTime::with_ymd_and_hms(2024, 1, 1, 8, 0, 0));
+        let end = start.add_duration(chrono::Duration::hours(1)).unwrap();
+
+        assert!(start.is_before(&end));
+        assert!(end.is_after(&start));
+        assert!(!start.is_after(&end));
+        assert!(!start.is_before(&start));
+        assert!(!start.is_before(&Value::from(1)));
+    }
+
+    #[test]
+    fn test_value_compact() {
+        use crate::json;
+
+        let value = json!({
+            "id": 7,
+            "name": "",
+            "note": null,
+            "meta": { "a": null, "b": { "c": null }, "d": [] },
+            "tags": ["x", null, {}, []],
+            "owner": { "name": "ana", "email": null }
+        });
+
+        assert_eq!(
+            value.clone().compact(true),
+            json!({
+                "id": 7,
+                "tags": ["x", null],
+                "owner": { "name": "ana" }
+            })
+        );
+        assert_eq!(value.compact(false).get("name"), Some(&"".to_value()));
+        assert_eq!(Value::from(3).compact(true), Value::from(3));
+    }
+
+    #[test]
+    fn test_value_update_at() {
+        let mut value = Value::from(vec![("user", Value::from(vec![("name", "ana")]))]);
+
+        assert!(value.update_at("/user/name", |name| *name = Value::from("bia")));
+        assert_eq!(value.pointer("/user/name"), Some(&"bia".to_value()));
+
+        let mut called = false;
+        assert!(!value.update_at("/user/email", |_| called = true));
+        assert!(!called);
+        assert_eq!(value.pointer("/user/email"), None);
+    }
+
+    #[test]
+    fn test_value_update_matching() {
+        let mut value = Value::from(vec![(
+            "items",
+            Value::from(vec![
+                Value::from(vec![("price", 10)]),
+                Value::from(vec![("price", 20)]),
+                Value::from(vec![("name", "no price")]),
+            ]),
+        )]);
+
+        let updated = value.update_matching("/items/*/price", |price| {
+            *price = price.clone() * Value::from(2)
+        });
+        assert_eq!(updated, 2);
+        assert_eq!(value.pointer("/items/0/price"), Some(&Value::from(20i64)));
+        assert_eq!(value.pointer("/items/1/price"), Some(&Value::from(40i64)));
+        assert_eq!(value.pointer("/items/2/price"), None);
+
+        let mut count = 0;
+        assert_eq!(value.update_matching("/*/*", |_| count += 1), 3);
+        assert_eq!(count, 3);
+        assert_eq!(value.update_matching("items", |_| {}), 0);
+    }
+
+    #[test]
+    fn test_value_increment() {
+        let mut stats = Value::from(Object::default());
+        for _ in 0..3 {
+            stats.increment("/stats/count", Number::from(1));
         }
+        assert_eq!(stats.pointer("/stats/count"), Some(&Value::from(3i64)));
 
-        assert_eq!(value.get("1").unwrap(), &1.43.to_value());
+        stats.increment("/stats/total", Number::from(1.5));
+        stats.increment("/stats/total", Number::from(2));
+        assert_eq!(stats.pointer("/stats/total"), Some(&Value::from(3.5)));
+    }
+
+    #[test]
+    fn test_value_merge_all() {
+        let defaults = Value::from(vec![
+            ("host", Value::from("localhost")),
+            ("port", Value::from(80)),
+            (
+                "log",
+                Value::from(vec![("level", "info"), ("format", "text")]),
+            ),
+        ]);
+        let file = Value::from(vec![
+            ("port", Value::from(8080)),
+            ("log", Value::from(vec![("level", "debug")])),
+        ]);
+        let env = Value::from(vec![("log", Value::from(vec![("format", "json")]))]);
+
+        let merged = Value::merge_all(&[defaults, file, env]);
+        assert_eq!(merged.pointer("/host"), Some(&"localhost".to_value()));
+        assert_eq!(merged.pointer("/port"), Some(&8080.to_value()));
+        assert_eq!(merged.pointer("/log/level"), Some(&"debug".to_value()));
+        assert_eq!(merged.pointer("/log/format"), Some(&"json".to_value()));
+
+        assert_eq!(Value::merge_all(&[]), Value::Null);
+        assert_eq!(
+            Value::merge_all(&[Value::from(vec![("a", 1)]), Value::from(2)]),
+            Value::from(2)
+        );
+    }
+
+    #[test]
+    fn test_value_merge_array_of_objects() {
+        use crate::json;
+
+        let fragments = json!([{"a": 1}, {"b": 2}, {"a": 3}]);
+        assert_eq!(fragments.merge_array_of_objects(), json!({"a": 3, "b": 2}));
+
+        let mixed = json!([{"db": {"host": "x", "port": 1}}, 7, null, {"db": {"port": 2}}]);
+        assert_eq!(
+            mixed.merge_array_of_objects(),
+            json!({"db": {"host": "x", "port": 2}})
+        );
+        assert_eq!(json!([1, 2]).merge_array_of_objects(), json!({}));
+    }
+
+    #[test]
+    fn test_value_fill_defaults() {
+        use crate::json;
+
+        let defaults = json!({
+            "host": "localhost",
+            "port": 80,
+            "log": {"level": "info", "format": "text"},
+            "tls": {"enabled": false}
+        });
+        let mut config = json!({
+            "port": 8080,
+            "log": {"level": "debug"},
+            "tls": null
+        });
+
+        config.fill_defaults(&defaults);
+        assert_eq!(config.get("host"), Some(&"localhost".to_value()));
+        assert_eq!(config.get("port"), Some(&8080.to_value()));
+        assert_eq!(config.pointer("/log/level"), Some(&"debug".to_value()));
+        assert_eq!(config.pointer("/log/format"), Some(&"text".to_value()));
+        assert_eq!(config.get("tls"), Some(&Value::Null));
+
+        let mut scalar = Value::from(1);
+        scalar.fill_defaults(&defaults);
+        assert_eq!(scalar, Value::from(1));
+    }
+
+    #[test]
+    fn test_value_zip() {
+        use crate::json;
+
+        let current = json!({"host": "localhost", "port": 80, "debug": true});
+        let next = json!({"port": 8080, "debug": true, "workers": 4});
+
+        assert_eq!(
+            current.zip(&next),
+            json!({
+                "debug": [true, true],
+                "host": ["localhost", null],
+                "port": [80, 8080],
+                "workers": [null, 4]
+            })
+        );
+        assert_eq!(
+            json!({"a": 1}).zip(&json!({"b": 2})),
+            json!({"a": [1, null], "b": [null, 2]})
+        );
+        assert_eq!(Value::Null.zip(&json!({"a": 1})), json!({"a": [null, 1]}));
+    }
+
+    #[test]
+    fn test_value_intersect() {
+        use crate::json;
+
+        let left = json!({
+            "name": "api",
+            "port": 80,
+            "tls": {"enabled": true, "cert": "a.pem"},
+            "tags": ["web", "v1"],
+            "only_left": 1
+        });
+        let right = json!({
+            "name": "api",
+            "port": 8080,
+            "tls": {"enabled": true, "key": "a.key"},
+            "tags": ["web", "v2", "extra"],
+            "only_right": 2
+        });
+
+        assert_eq!(
+            left.intersect(&right),
+            json!({
+                "name": "api",
+                "port": null,
+                "tls": {"enabled": true},
+                "tags": ["web", null]
+            })
+        );
+        assert_eq!(Value::from(1).intersect(&Value::from("1")), Value::Null);
+    }
+
+    #[test]
+    fn test_value_get_or_default() {
+        let config = Value::from(vec![
+            ("debug", Value::from(true)),
+            ("port", Value::from(8080)),
+            ("ratio", Value::from(0.5)),
+            ("name", Value::from("api")),
+        ]);
+
+        assert!(config.get_bool_or("/debug", false));
+        assert_eq!(config.get_i64_or("/port", 80), 8080);
+        assert_eq!(config.get_f64_or("/ratio", 1.0), 0.5);
+        assert_eq!(config.get_f64_or("/port", 1.0), 8080.0);
+        assert_eq!(config.get_str_or("/name", "default"), "api");
+
+        assert!(!config.get_bool_or("/missing", false));
+        assert_eq!(config.get_i64_or("/missing/deep", 80), 80);
+        assert_eq!(config.get_str_or("/missing", "default"), "default");
+
+        assert!(config.get_bool_or("/name", true));
+        assert_eq!(config.get_i64_or("/ratio", 80), 80);
+        assert_eq!(config.get_f64_or("/name", 1.0), 1.0);
+        assert_eq!(config.get_str_or("/port", "default"), "default");
+    }
+
+    #[test]
+    fn test_value_strip_nulls() {
+        let original = Value::from(vec![
+            ("a", Value::Null),
+            ("b", Value::from(vec![("c", Value::Null)])),
+            ("d", Value::from(vec![Value::Null, Value::from(1)])),
+            ("e", Value::from(2)),
+        ]);
+
+        let mut value = original.clone();
+        value.strip_nulls(false, false);
+        assert_eq!(
+            value,
+            Value::from(vec![
+                ("b", Value::from(Vec::<(String, Value)>::new())),
+                ("d", Value::from(vec![Value::Null, Value::from(1)])),
+                ("e", Value::from(2)),
+            ])
+        );
+
+        let mut value = original.clone();
+        value.strip_nulls(true, true);
+        assert_eq!(
+            value,
+            Value::from(vec![
+                ("d", Value::from(vec![Value::from(1)])),
+                ("e", Value::from(2)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_value_expect_type() {
+        let value = Value::from(vec![("list", vec![1, 2])]);
+
+        assert_eq!(value.expect_object().unwrap().len(), 1);
+        let list = value.get("list").unwrap();
+        assert_eq!(list.expect_array().unwrap().len(), 2);
+        assert!(Value::from("a").expect_string().is_ok());
+        assert!(Value::from(1).expect_number().is_ok());
+
+        let error = list.expect_object().unwrap_err();
+        assert_eq!(
+            error,
+            Error::TypeMismatch {
+                expected: "object",
+                found: "array"
+            }
+        );
+        assert_eq!(error.to_string(), "expected object, found array");
+        assert_eq!(
+            Value::Null.expect_string().unwrap_err().to_string(),
+            "expected string, found null"
+        );
+    }
+
+    #[test]
+    fn test_value_normalize_numbers() {
+        let mut value = Value::from(vec![
+            ("a", Value::from(3.0)),
+            ("b", Value::from(vec![Value::from(4.0), Value::from(2.5)])),
+            ("c", Value::from("3.0")),
+        ]);
+
+        value.normalize_numbers(false);
+        assert_eq!(value.get("a"), Some(&Number::from(3i64).to_value()));
+        assert_eq!(
+            value.get("b"),
+            Some(&Value::from(vec![
+                Number::from(4i64).to_value(),
+                Value::from(2.5)
+            ]))
+        );
+        assert_eq!(value.get("c"), Some(&Value::from("3.0")));
+
+        value.normalize_numbers(true);
+        assert_eq!(value.get("a"), Some(&Value::from(3.0)));
+    }
+
+    #[test]
+    fn test_value_contains_value() {
+        let value = Value::from(vec![(
+            "groups",
+            Value::from(vec![
+                Value::from(vec!["a", "b"]),
+                Value::from(vec!["needle"]),
+            ]),
+        )]);
+
+        assert!(value.contains_value(&"needle".to_value()));
+        assert!(value.contains_value(&value));
+        assert!(!value.contains_value(&"missing".to_value()));
+    }
+
+    #[test]
+    fn test_value_add_assign() {
+        let mut list = Value::from(vec![1, 2]);
+        list += Value::from(vec![3]);
+        assert_eq!(list, Value::from(vec![1, 2, 3]));
+
+        let mut text = Value::from("hello");
+        text += Value::from(", world");
+        assert_eq!(text, Value::from("hello, world"));
+
+        let mut object = Value::from(vec![("a", 1), ("b", 2)]);
+        object += Value::from(vec![("b", 3), ("c", 4)]);
+        assert_eq!(object, Value::from(vec![("a", 1), ("b", 3), ("c", 4)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_value_add_assign_mismatch() {
+        let mut list = Value::from(vec![1]);
+        list += Value::from("a");
+    }
+
+    #[test]
+    fn test_value_add_matches_add_assign() {
+        let cases = vec![
+            (Value::from(2), Value::from(3)),
+            (Value::from(vec![1, 2]), Value::from(vec![3])),
+            (Value::from("ab"), Value::from("cd")),
+            (
+                Value::from(vec![("a", 1), ("b", 2)]),
+                Value::from(vec![("b", 3), ("c", 4)]),
+            ),
+        ];
+
+        for (left, right) in cases {
+            let mut assigned = left.clone();
+            assigned += right.clone();
+            assert_eq!(left + right, assigned);
+        }
+
+        let mut number = Value::from(2);
+        number += Value::from(3);
+        assert_eq!(number, Value::from(5i64));
+        assert_eq!(Value::from(vec![1]) + Value::from(1), Value::Null);
+        assert_eq!(
+            Value::from(vec![1, 2]) + Value::from(vec![3]),
+            Value::from(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_value_deep_contains_key() {
+        use crate::json;
+
+        let users = json!({
+            "users": [
+                { "name": "Ana", "email": "ana@example.com" },
+                { "name": "Bo", "contact": { "email": "bo@example.com" } },
+                { "name": "Cy" }
+            ]
+        });
+
+        assert!(users.deep_contains_key("email"));
+        assert!(!users.deep_contains_key("phone"));
+
+        let mut emails = users
+            .deep_get_all("email")
+            .into_iter()
+            .map(|email| email.to_string())
+            .collect::<Vec<_>>();
+        emails.sort();
+        assert_eq!(emails, vec!["ana@example.com", "bo@example.com"]);
+        assert!(users.deep_get_all("phone").is_empty());
+    }
+
+    #[test]
+    fn test_value_collect_strings() {
+        use crate::json;
+
+        let user = json!({
+            "name": "Ana",
+            "age": 31,
+            "emails": ["ana@example.com", "a@work.example"],
+            "address": {"city": "Recife", "zip": null},
+            "a/b": "x"
+        });
+
+        assert_eq!(
+            user.collect_strings(),
+            vec!["x", "Recife", "ana@example.com", "a@work.example", "Ana"]
+        );
+        assert_eq!(
+            user.collect_strings_with_paths(),
+            vec![
+                ("/a~1b".to_string(), "x"),
+                ("/address/city".to_string(), "Recife"),
+                ("/emails/0".to_string(), "ana@example.com"),
+                ("/emails/1".to_string(), "a@work.example"),
+                ("/name".to_string(), "Ana"),
+            ]
+        );
+        assert!(Value::from(1).collect_strings().is_empty());
+    }
+
+    #[test]
+    fn test_value_find_paths() {
+        let value = Value::from(vec![
+            ("a/b", Value::from(7)),
+            ("list", Value::from(vec![1, 7, 7])),
+        ]);
+
+        let mut paths = value.find_paths(&7.to_value());
+        paths.sort();
+        assert_eq!(paths, vec!["/a~1b", "/list/1", "/list/2"]);
+        assert_eq!(value.find_paths(&value), vec![""]);
+        assert!(value.find_paths(&8.to_value()).is_empty());
+    }
+
+    #[test]
+    fn test_value_object_values_and_entries() {
+        let value = Value::from(vec![("a", 1), ("b", 2), ("c", 3)]);
+
+        let mut entries = value.object_entries();
+        entries.sort_by_key(|(key, _)| *key);
+        assert_eq!(
+            entries,
+            vec![
+                ("a", &1.to_value()),
+                ("b", &2.to_value()),
+                ("c", &3.to_value())
+            ]
+        );
+        assert_eq!(value.object_values().len(), 3);
+
+        let value = Value::from(vec![1, 2]);
+        assert!(value.object_entries().is_empty());
+        assert!(value.object_values().is_empty());
+    }
+
+    #[test]
+    fn test_value_object_get_ci() {
+        let mut value = Value::from(vec![("content-type", "application/json")]);
+
+        assert_eq!(
+            value.object_get_ci("Content-Type"),
+            Some(&"application/json".to_value())
+        );
+        assert_eq!(value.object_get_ci("Accept"), None);
+        assert_eq!(Value::from(1).object_get_ci("Content-Type"), None);
+
+        if let Some(item) = value.object_get_ci_mut("CONTENT-TYPE") {
+            *item = "text/plain".to_value();
+        }
+        assert_eq!(value.get("content-type"), Some(&"text/plain".to_value()));
+    }
+
+    #[test]
+    fn test_value_is_subset_of() {
+        use crate::json;
+
+        let full = json!({
+            "method": "GET",
+            "headers": {"accept": "json", "host": "example.com"},
+            "tags": ["a", "b", {"id": 1, "name": "c"}]
+        });
+
+        let partial = json!({"method": "GET", "headers": {"accept": "json"}});
+        assert!(partial.is_subset_of(&full));
+        assert!(full.is_subset_of(&full));
+        assert!(!full.is_subset_of(&partial));
+
+        let differing = json!({"method": "POST"});
+        assert!(!differing.is_subset_of(&full));
+
+        let elements = json!({"tags": ["b", {"id": 1}]});
+        assert!(elements.is_subset_of(&full));
+        assert!(!json!({"tags": ["z"]}).is_subset_of(&full));
+
+        assert!(Value::from(1).is_subset_of(&Value::from(1)));
+        assert!(!Value::from(1).is_subset_of(&Value::from("1")));
+    }
+
+    #[test]
+    fn test_value_as_array_coerced() {
+        let scalar = Value::from("a");
+        assert_eq!(scalar.as_array_coerced(), vec![&scalar]);
+
+        let array = Value::from(vec!["a", "b"]);
+        assert_eq!(
+            array.as_array_coerced(),
+            vec![&"a".to_value(), &"b".to_value()]
+        );
+
+        let object = Value::from(vec![("a", 1)]);
+        assert_eq!(object.as_array_coerced(), vec![&object]);
+
+        assert!(Value::Null.as_array_coerced().is_empty());
+        assert!(Value::from(Vec::<Value>::new())
+            .as_array_coerced()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_value_entries() {
+        let value = Value::from(vec![("z", 1), ("a", 2)]);
+        let mut entries = value.clone().into_entries();
+        assert_eq!(entries[0], ("a".to_string(), Value::from(2)));
+
+        entries.retain(|(key, _)| key != "z");
+        assert_eq!(Value::from_entries(entries), Value::from(vec![("a", 2)]));
+    }
+
+    #[test]
+    fn test_value_object_rename_key() {
+        let mut value = Value::from(vec![("user_name", "ana"), ("role", "admin")]);
+
+        assert!(value.object_rename_key("user_name", "userName"));
+        assert_eq!(value.get("userName"), Some(&"ana".to_value()));
+        assert_eq!(value.get("user_name"), None);
+        assert_eq!(value.len(), 2);
+
+        assert!(value.object_rename_key("role", "userName"));
+        assert_eq!(value.get("userName"), Some(&"admin".to_value()));
+        assert_eq!(value.len(), 1);
+
+        assert!(!value.object_rename_key("missing", "other"));
+        assert!(!Value::from(1).object_rename_key("a", "b"));
     }
 
     #[test]
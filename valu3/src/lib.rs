@@ -28,6 +28,7 @@ pub mod impls;
 pub mod macros;
 pub mod prelude;
 pub mod primitives;
+pub mod schema;
 #[cfg(feature = "serde")]
 pub mod serde_value;
 pub mod to;
@@ -40,6 +41,9 @@ pub mod value;
 #[macro_use]
 extern crate pest_derive;
 
+// Lets `::valu3::...` paths emitted by the derive macros resolve inside this crate too.
+extern crate self as valu3;
+
 #[cfg(feature = "parser")]
 pub mod parser;
 
@@ -50,7 +54,54 @@ pub enum Error {
     #[cfg(feature = "parser")]
     NonParseble,
     NotNumber,
+    NotString,
+    PointerNotFound(String),
+    InvalidBase64,
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+    InvalidCast {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// An array element at `index` could not be converted to the requested type.
+    InvalidElement {
+        index: usize,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "parser")]
+            Error::NonParsebleMsg(msg) => write!(f, "{}", msg),
+            #[cfg(feature = "parser")]
+            Error::NonParseble => write!(f, "unable to parse value"),
+            Error::NotNumber => write!(f, "value is not a number"),
+            Error::NotString => write!(f, "value is not a string"),
+            Error::PointerNotFound(pointer) => write!(f, "no value found at pointer {}", pointer),
+            Error::InvalidBase64 => write!(f, "invalid base64 content"),
+            Error::TypeMismatch { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            Error::InvalidCast { from, to } => write!(f, "cannot cast {} to {}", from, to),
+            Error::InvalidElement {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "element {}: expected {}, found {}",
+                index, expected, found
+            ),
+        }
+    }
 }
 
+impl std::error::Error for Error {}
+
 #[cfg(test)]
 mod tests;
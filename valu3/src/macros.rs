@@ -23,6 +23,47 @@ macro_rules! json {
         HashMap::<String, $crate::value::Value>::new().to_value()
     }};
 
+    // Handle object spread: entries of an existing object are copied first, literal entries win
+    ({ .. $base:expr, $($key:tt : $value:tt),* $(,)? }) => {{
+        use std::collections::HashMap;
+        use $crate::traits::ToValueBehavior;
+        let mut map = HashMap::new();
+        match ($base).to_value() {
+            $crate::value::Value::Object(object) => {
+                for (key, value) in object.iter() {
+                    map.insert(key.to_string(), value.clone());
+                }
+            }
+            _ => panic!("Unable to spread a type other than an object"),
+        }
+        $(
+            map.insert($key.to_string(), json!($value));
+        )*
+        map.to_value()
+    }};
+
+    ({ .. $base:expr, $($key:tt : $value:expr),* $(,)? }) => {{
+        use std::collections::HashMap;
+        use $crate::traits::ToValueBehavior;
+        let mut map = HashMap::new();
+        match ($base).to_value() {
+            $crate::value::Value::Object(object) => {
+                for (key, value) in object.iter() {
+                    map.insert(key.to_string(), value.clone());
+                }
+            }
+            _ => panic!("Unable to spread a type other than an object"),
+        }
+        $(
+            map.insert($key.to_string(), json!($value));
+        )*
+        map.to_value()
+    }};
+
+    ({ .. $base:expr }) => {
+        json!({ .. $base, })
+    };
+
     // Handle object with key-value pairs (values that are token-trees — e.g. nested objects/arrays)
     ({ $($key:tt : $value:tt),* $(,)? }) => {{
         use std::collections::HashMap;
@@ -222,6 +263,46 @@ mod test {
         assert_eq!(data, expected_map.to_value());
     }
 
+    #[test]
+    fn test_json_spread() {
+        let base = json!({
+            "name": "base",
+            "value": 1
+        });
+        let data = json!({
+            ..base,
+            "value": 2,
+            "extra": true
+        });
+
+        let expected = {
+            let mut map = HashMap::new();
+            map.insert("name".to_string(), "base".to_value());
+            map.insert("value".to_string(), 2.to_value());
+            map.insert("extra".to_string(), true.to_value());
+            map.to_value()
+        };
+
+        assert_eq!(data, expected);
+        assert_eq!(json!({ ..base }), base);
+    }
+
+    #[test]
+    fn test_json_spread_with_expressions() {
+        let base = json!({ "count": 1 });
+        let count = 41;
+        let data = json!({ ..base, "count": count + 1 });
+
+        assert_eq!(data.get("count"), Some(&42.to_value()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_json_spread_non_object() {
+        let base = json!([1, 2]);
+        let _ = json!({ ..base, "extra": 1 });
+    }
+
     #[test]
     fn test_recursive_json() {
         let inner = json!({
@@ -0,0 +1,229 @@
+//! A JSON parser that borrows string slices from the input buffer.
+//!
+//! Strings without escape sequences are kept as `Cow::Borrowed` slices of the
+//! source, so scanning large documents only allocates for escaped strings and
+//! containers. Use `into_owned` (or `to_value`) to convert the result into a
+//! regular `Value`; escapes are resolved exactly as `Value::json_to_value`
+//! resolves them, so both give the same `Value`.
+use super::{JSONParser, Rule};
+use crate::prelude::*;
+use pest::iterators::Pair;
+use pest::Parser;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// A JSON value whose strings may borrow from the parsed input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedValue<'a> {
+    String(Cow<'a, str>),
+    Number(Number),
+    Boolean(bool),
+    Array(Vec<BorrowedValue<'a>>),
+    Object(Vec<(Cow<'a, str>, BorrowedValue<'a>)>),
+    Null,
+}
+
+impl<'a> BorrowedValue<'a> {
+    /// Parses a JSON document, borrowing every string that needs no unescaping.
+    pub fn parse_json(str: &'a str) -> Result<BorrowedValue<'a>, Error> {
+        match JSONParser::parse(Rule::json, str.trim()) {
+            Ok(mut pairs) => match pairs.next() {
+                Some(pair) => Ok(Self::parse_inner(pair)),
+                None => Err(Error::NonParseble),
+            },
+            Err(msg) => Err(Error::NonParsebleMsg(msg.to_string())),
+        }
+    }
+
+    fn parse_inner(pair: Pair<'a, Rule>) -> Self {
+        match pair.as_rule() {
+            Rule::object => BorrowedValue::Object(
+                pair.into_inner()
+                    .map(|pair| {
+                        let mut inner_rules = pair.into_inner();
+                        let name = unescape(
+                            inner_rules
+                                .next()
+                                .unwrap()
+                                .into_inner()
+                                .next()
+                                .unwrap()
+                                .as_str(),
+                        );
+                        let value = Self::parse_inner(inner_rules.next().unwrap());
+                        (name, value)
+                    })
+                    .collect(),
+            ),
+            Rule::array => BorrowedValue::Array(pair.into_inner().map(Self::parse_inner).collect()),
+            Rule::string => {
+                BorrowedValue::String(unescape(pair.into_inner().next().unwrap().as_str()))
+            }
+            Rule::number => BorrowedValue::Number(Number::try_from(pair.as_str()).unwrap()),
+            Rule::boolean => BorrowedValue::Boolean(pair.as_str().parse().unwrap()),
+            Rule::null
+            | Rule::json
+            | Rule::EOI
+            | Rule::key_value_pair
+            | Rule::value
+            | Rule::inner
+            | Rule::char
+            | Rule::WHITESPACE => BorrowedValue::Null,
+        }
+    }
+
+    /// Returns `true` if this is a string that still borrows from the input.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, BorrowedValue::String(Cow::Borrowed(_)))
+    }
+
+    /// Converts into an owned `Value`, copying any borrowed strings.
+    pub fn into_owned(self) -> Value {
+        match self {
+            BorrowedValue::String(s) => StringB::from(s.into_owned()).to_value(),
+            BorrowedValue::Number(n) => Value::Number(n),
+            BorrowedValue::Boolean(b) => Value::Boolean(b),
+            BorrowedValue::Array(items) => Value::from(
+                items
+                    .into_iter()
+                    .map(BorrowedValue::into_owned)
+                    .collect::<Vec<_>>(),
+            ),
+            BorrowedValue::Object(entries) => Value::from(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect::<HashMap<String, Value>>(),
+            ),
+            BorrowedValue::Null => Value::Null,
+        }
+    }
+}
+
+impl<'a> ToValueBehavior for BorrowedValue<'a> {
+    fn to_value(&self) -> Value {
+        self.clone().into_owned()
+    }
+}
+
+/// Resolves JSON escape sequences, borrowing the input when there are none.
+pub(crate) fn unescape(raw: &str) -> Cow<'_, str> {
+    if !raw.contains('\\') {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('b') => out.push('\u{0008}'),
+            Some('f') => out.push('\u{000C}'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('u') => {
+                let high = read_hex4(&mut chars);
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    let mut lookahead = chars.clone();
+                    if lookahead.next() == Some('\\') && lookahead.next() == Some('u') {
+                        let low = read_hex4(&mut lookahead);
+                        if (0xDC00..0xE000).contains(&low) {
+                            chars = lookahead;
+                            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                        } else {
+                            high
+                        }
+                    } else {
+                        high
+                    }
+                } else {
+                    high
+                };
+                out.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+            }
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    Cow::Owned(out)
+}
+
+fn read_hex4(chars: &mut std::str::Chars<'_>) -> u32 {
+    let digits: String = chars.by_ref().take(4).collect();
+    u32::from_str_radix(&digits, 16).unwrap_or(0xFFFD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn borrows_unescaped_strings() {
+        let raw = r#"{"name": "plain", "quote": "say \"hi\"", "list": ["a", "b\nc"]}"#;
+        let value = BorrowedValue::parse_json(raw).unwrap();
+
+        let entries = match &value {
+            BorrowedValue::Object(entries) => entries,
+            _ => panic!("Expected object"),
+        };
+        let get = |key: &str| {
+            entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v)
+                .unwrap()
+        };
+
+        assert!(get("name").is_borrowed());
+        assert!(!get("quote").is_borrowed());
+        assert_eq!(
+            get("quote"),
+            &BorrowedValue::String(Cow::Borrowed("say \"hi\""))
+        );
+
+        let owned = value.into_owned();
+        assert_eq!(owned.get("name"), Some(&"plain".to_value()));
+        assert_eq!(owned.get("quote"), Some(&"say \"hi\"".to_value()));
+        assert_eq!(owned.get("list"), Some(&Value::from(vec!["a", "b\nc"])));
+    }
+
+    #[test]
+    fn unescapes_unicode_and_surrogate_pairs() {
+        let value = BorrowedValue::parse_json(r#""caf\u00e9 \ud83d\ude80""#).unwrap();
+        assert!(!value.is_borrowed());
+        assert_eq!(value.to_value(), "café 🚀".to_value());
+    }
+
+    #[test]
+    fn benchmark_borrowed_parse_matches_owned() {
+        let items = (0..500)
+            .map(|i| {
+                format!(
+                    r#"{{"id": {}, "name": "item{}", "note": "tab\t\u00e9", "active": true, "tags": ["x", "y"]}}"#,
+                    i, i
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let raw = format!("[{}]", items);
+
+        let start = Instant::now();
+        let borrowed = BorrowedValue::parse_json(&raw).unwrap();
+        let borrowed_duration = start.elapsed();
+
+        let start = Instant::now();
+        let owned = Value::json_to_value(&raw).unwrap();
+        let owned_duration = start.elapsed();
+
+        println!("Borrowed parse: {:?}", borrowed_duration);
+        println!("Owned parse:    {:?}", owned_duration);
+
+        assert_eq!(borrowed.into_owned(), owned);
+    }
+}
@@ -0,0 +1,342 @@
+//! An event-based (SAX-style) view of a JSON document.
+//!
+//! `Value::parse_json_events` scans the input incrementally and reports each
+//! structural element to a callback as soon as it is read, instead of
+//! assembling a `Value` tree, so callers can pick out the few fields they need
+//! and drop the rest. Memory use is bounded by the nesting depth and the size
+//! of the largest single scalar, not by the size of the document.
+//!
+//! Because events are emitted while scanning, a malformed document reports
+//! the events that precede the error before `parse_json_events` returns it.
+use super::borrowed::unescape;
+use super::render_context;
+use crate::prelude::*;
+use std::borrow::Cow;
+
+/// A single step of a JSON document, in document order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonEvent<'a> {
+    StartObject,
+    /// An object key; the next event is (or starts) its value.
+    Key(Cow<'a, str>),
+    EndObject,
+    StartArray,
+    EndArray,
+    /// A string, number, boolean or null.
+    Scalar(Value),
+}
+
+impl Value {
+    /// Scans `input` and calls `handler` for every event, without building the tree.
+    ///
+    /// The grammar is the one `json_to_value` accepts. On malformed input the events
+    /// read so far have already been delivered when the error is returned.
+    pub fn parse_json_events<'a, F>(input: &'a str, mut handler: F) -> Result<(), Error>
+    where
+        F: FnMut(JsonEvent<'a>),
+    {
+        EventReader { input, pos: 0 }.run(&mut handler)
+    }
+}
+
+/// The kind of container the scanner is inside of; only this stack is kept.
+enum Container {
+    Object,
+    Array,
+}
+
+struct EventReader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> EventReader<'a> {
+    fn run<F>(&mut self, handler: &mut F) -> Result<(), Error>
+    where
+        F: FnMut(JsonEvent<'a>),
+    {
+        let mut stack = Vec::new();
+        let mut expect_value = true;
+
+        loop {
+            self.skip_whitespace();
+            if expect_value {
+                expect_value = false;
+                if self.eat("{") || self.eat("#{") {
+                    handler(JsonEvent::StartObject);
+                    self.skip_whitespace();
+                    if self.eat("}") {
+                        handler(JsonEvent::EndObject);
+                    } else {
+                        stack.push(Container::Object);
+                        self.key(handler)?;
+                        expect_value = true;
+                    }
+                } else if self.eat("[") {
+                    handler(JsonEvent::StartArray);
+                    self.skip_whitespace();
+                    if self.eat("]") {
+                        handler(JsonEvent::EndArray);
+                    } else {
+                        stack.push(Container::Array);
+                        expect_value = true;
+                    }
+                } else {
+                    handler(JsonEvent::Scalar(self.scalar()?));
+                }
+                continue;
+            }
+
+            match stack.last() {
+                None if self.pos == self.input.len() => return Ok(()),
+                None => return Err(self.error("expected end of input")),
+                Some(Container::Object) => {
+                    if self.eat(",") {
+                        self.skip_whitespace();
+                        self.key(handler)?;
+                        expect_value = true;
+                    } else if self.eat("}") {
+                        stack.pop();
+                        handler(JsonEvent::EndObject);
+                    } else {
+                        return Err(self.error("expected `,` or `}`"));
+                    }
+                }
+                Some(Container::Array) => {
+                    if self.eat(",") {
+                        expect_value = true;
+                    } else if self.eat("]") {
+                        stack.pop();
+                        handler(JsonEvent::EndArray);
+                    } else {
+                        return Err(self.error("expected `,` or `]`"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads `"key":` and emits the key.
+    fn key<F>(&mut self, handler: &mut F) -> Result<(), Error>
+    where
+        F: FnMut(JsonEvent<'a>),
+    {
+        if !self.rest().starts_with('"') {
+            return Err(self.error("expected string"));
+        }
+        let raw = self.string()?;
+        handler(JsonEvent::Key(unescape(raw)));
+        self.skip_whitespace();
+        if self.eat(":") {
+            Ok(())
+        } else {
+            Err(self.error("expected `:`"))
+        }
+    }
+
+    fn scalar(&mut self) -> Result<Value, Error> {
+        let rest = self.rest();
+        if rest.starts_with('"') {
+            let raw = self.string()?;
+            Ok(StringB::from(unescape(raw).into_owned()).to_value())
+        } else if self.eat("true") {
+            Ok(Value::Boolean(true))
+        } else if self.eat("false") {
+            Ok(Value::Boolean(false))
+        } else if self.eat("null") {
+            Ok(Value::Null)
+        } else if rest.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            self.number()
+        } else {
+            Err(self.error("expected value"))
+        }
+    }
+
+    /// Reads a quoted string and returns its raw contents, escapes still in place.
+    fn string(&mut self) -> Result<&'a str, Error> {
+        let input = self.input;
+        let start = self.pos + 1;
+        let mut chars = input[start..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos = start + index + 1;
+                    return Ok(&input[start..start + index]);
+                }
+                '\\' => {
+                    let valid = match chars.next() {
+                        Some((_, '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't')) => true,
+                        Some((_, 'u')) => (0..4).all(|_| {
+                            chars
+                                .next()
+                                .is_some_and(|(_, digit)| digit.is_ascii_hexdigit())
+                        }),
+                        _ => false,
+                    };
+                    if !valid {
+                        self.pos = start + index;
+                        return Err(self.error("invalid escape sequence"));
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.pos = self.input.len();
+        Err(self.error("unterminated string"))
+    }
+
+    fn number(&mut self) -> Result<Value, Error> {
+        let start = self.pos;
+        self.eat("-");
+        if !self.eat("0") && self.digits() == 0 {
+            return Err(self.error("expected digit"));
+        }
+        if self.eat(".") {
+            self.digits();
+        }
+        if self.eat("e") || self.eat("E") {
+            if !self.eat("+") {
+                self.eat("-");
+            }
+            if self.digits() == 0 {
+                return Err(self.error("expected digit"));
+            }
+        }
+        let raw = &self.input[start..self.pos];
+        Number::try_from(raw).map(Value::Number).map_err(|_| {
+            self.pos = start;
+            self.error(&format!("invalid number `{}`", raw))
+        })
+    }
+
+    fn digits(&mut self) -> usize {
+        let count = self
+            .rest()
+            .bytes()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        self.pos += count;
+        count
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\r', '\n']).len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let matched = self.rest().starts_with(token);
+        if matched {
+            self.pos += token.len();
+        }
+        matched
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::NonParsebleMsg(render_context(self.input, self.pos, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emits_events_in_document_order() {
+        let raw = r#"{"name": "a\"b", "tags": [1, true, null], "empty": {}}"#;
+        let mut events = Vec::new();
+        Value::parse_json_events(raw, |event| events.push(event)).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                JsonEvent::StartObject,
+                JsonEvent::Key(Cow::Borrowed("name")),
+                JsonEvent::Scalar("a\"b".to_value()),
+                JsonEvent::Key(Cow::Borrowed("tags")),
+                JsonEvent::StartArray,
+                JsonEvent::Scalar(1.to_value()),
+                JsonEvent::Scalar(Value::Boolean(true)),
+                JsonEvent::Scalar(Value::Null),
+                JsonEvent::EndArray,
+                JsonEvent::Key(Cow::Borrowed("empty")),
+                JsonEvent::StartObject,
+                JsonEvent::EndObject,
+                JsonEvent::EndObject,
+            ]
+        );
+    }
+
+    #[test]
+    fn selects_fields_without_building_the_tree() {
+        let raw = r#"[{"id": 1, "name": "x"}, {"id": 2, "name": "y"}]"#;
+        let mut ids = Vec::new();
+        let mut take_next = false;
+        Value::parse_json_events(raw, |event| match event {
+            JsonEvent::Key(key) => take_next = key == "id",
+            JsonEvent::Scalar(value) if take_next => {
+                ids.push(value);
+                take_next = false;
+            }
+            _ => {}
+        })
+        .unwrap();
+
+        assert_eq!(ids, vec![1.to_value(), 2.to_value()]);
+    }
+
+    #[test]
+    fn reports_errors_after_the_preceding_events() {
+        let mut events = Vec::new();
+        let result = Value::parse_json_events(r#"{"a": [1, 2}"#, |event| events.push(event));
+
+        assert!(result.is_err());
+        assert_eq!(
+            events,
+            vec![
+                JsonEvent::StartObject,
+                JsonEvent::Key(Cow::Borrowed("a")),
+                JsonEvent::StartArray,
+                JsonEvent::Scalar(1.to_value()),
+                JsonEvent::Scalar(2.to_value()),
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_what_json_to_value_accepts() {
+        for raw in [
+            r#" {"k\u00e9y": "\ud83d\ude80", "n": [-0.5e3, 0, 1.], "o": #{}} "#,
+            "[]",
+            "\"text\"",
+            "-12",
+        ] {
+            let mut count = 0;
+            assert!(Value::json_to_value(raw).is_ok(), "{}", raw);
+            assert!(
+                Value::parse_json_events(raw, |_| count += 1).is_ok(),
+                "{}",
+                raw
+            );
+            assert!(count > 0);
+        }
+
+        for raw in [
+            "",
+            "[1,]",
+            "{\"a\" 1}",
+            "[1] x",
+            "tru",
+            "01",
+            "\"\\x\"",
+            "\"open",
+        ] {
+            assert!(Value::json_to_value(raw).is_err(), "{}", raw);
+            assert!(Value::parse_json_events(raw, |_| {}).is_err(), "{}", raw);
+        }
+    }
+}
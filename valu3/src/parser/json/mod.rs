@@ -1,6 +1,10 @@
+pub mod borrowed;
+pub mod events;
+pub mod spanned;
+
 use crate::prelude::*;
 use pest::Parser;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Parser)]
 #[grammar = "parser/json/json.pest"]
@@ -8,46 +12,195 @@ struct JSONParser;
 
 use pest::iterators::Pair;
 
+/// How `Value::json_to_value_opts` handles an object that repeats a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeyPolicy {
+    /// The last occurrence wins, as in `json_to_value`.
+    #[default]
+    KeepLast,
+    /// The first occurrence wins.
+    KeepFirst,
+    /// Parsing fails on the first repeated key.
+    Error,
+    /// All occurrences are collected, in order, into an array.
+    Combine,
+}
+
+/// Options controlling how `Value::json_to_value_opts` reads a document.
+///
+/// A bare `DuplicateKeyPolicy` converts into options with every other setting at
+/// its default, so `json_to_value_opts(raw, DuplicateKeyPolicy::KeepFirst)` works.
+///
+/// # Example
+///
+/// ```no_run
+/// # use valu3::prelude::*;
+/// let options = JsonParseOptions::new()
+///     .duplicates(DuplicateKeyPolicy::KeepFirst)
+///     .case_fold_keys(true);
+/// let value = Value::json_to_value_opts(r#"{"Name": 1, "name": 2}"#, options).unwrap();
+/// assert_eq!(value.get("Name"), Some(&1.to_value()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JsonParseOptions {
+    /// How an object that repeats a key is resolved.
+    pub duplicates: DuplicateKeyPolicy,
+    /// Treats keys that differ only in case as repeats of each other.
+    pub case_fold_keys: bool,
+    /// Reads integer literals exactly, as `json_to_value_relaxed_numbers` does.
+    pub relaxed_numbers: bool,
+}
+
+impl JsonParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn duplicates(mut self, duplicates: DuplicateKeyPolicy) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    pub fn case_fold_keys(mut self, case_fold_keys: bool) -> Self {
+        self.case_fold_keys = case_fold_keys;
+        self
+    }
+
+    pub fn relaxed_numbers(mut self, relaxed_numbers: bool) -> Self {
+        self.relaxed_numbers = relaxed_numbers;
+        self
+    }
+}
+
+impl From<DuplicateKeyPolicy> for JsonParseOptions {
+    fn from(duplicates: DuplicateKeyPolicy) -> Self {
+        Self::new().duplicates(duplicates)
+    }
+}
+
 impl Value {
+    /// Parses a JSON document. A leading UTF-8 byte order mark and surrounding
+    /// whitespace are ignored. Escape sequences in keys and strings are resolved,
+    /// as `BorrowedValue::parse_json` does.
     pub fn json_to_value(str: &str) -> Result<Value, Error> {
-        let value = match JSONParser::parse(Rule::json, str.trim()) {
+        Self::json_to_value_opts(str, JsonParseOptions::default())
+    }
+
+    /// Parses a JSON document, resolving repeated keys with `options.duplicates`.
+    ///
+    /// With `case_fold_keys`, keys that differ only in case count as repeats of each
+    /// other, so `{"Name": 1, "name": 2}` yields the single entry `"name": 2` under
+    /// `KeepLast`. The surviving entry keeps its own spelling; combined entries keep
+    /// the spelling of the first occurrence.
+    pub fn json_to_value_opts<O>(str: &str, options: O) -> Result<Value, Error>
+    where
+        O: Into<JsonParseOptions>,
+    {
+        Self::json_parse_document(str, options.into())
+    }
+
+    /// Parses a JSON document without losing precision on large integers.
+    ///
+    /// Integer literals are read exactly as 64 or 128-bit integers instead of falling
+    /// back to `f64`, and those too large even for `u128` or `i128` are kept verbatim as
+    /// `Value::String`, so the rest of the document still parses. Literals with a
+    /// fraction or exponent are read as in `json_to_value`.
+    pub fn json_to_value_relaxed_numbers(str: &str) -> Result<Value, Error> {
+        Self::json_parse_document(str, JsonParseOptions::new().relaxed_numbers(true))
+    }
+
+    fn json_parse_document(str: &str, options: JsonParseOptions) -> Result<Value, Error> {
+        let str = str.strip_prefix('\u{FEFF}').unwrap_or(str);
+        match JSONParser::parse(Rule::json, str.trim()) {
             Ok(mut pairs) => match pairs.next() {
-                Some(pair) => Self::json_parse_value_inner(pair),
-                None => return Err(Error::NonParseble),
+                Some(pair) => Self::json_parse_value_inner(pair, options),
+                None => Err(Error::NonParseble),
             },
-            Err(msg) => return Err(Error::NonParsebleMsg(msg.to_string())),
-        };
-        Ok(value)
+            Err(msg) => Err(parse_error(str, &msg)),
+        }
     }
 
-    fn json_parse_value_inner(pair: Pair<Rule>) -> Self {
-        match pair.as_rule() {
+    fn json_parse_value_inner(pair: Pair<Rule>, options: JsonParseOptions) -> Result<Self, Error> {
+        let value = match pair.as_rule() {
             Rule::object => {
-                let map = pair
-                    .into_inner()
-                    .map(|pair| {
-                        let mut inner_rules = pair.into_inner();
-                        let name = inner_rules
+                let mut map: HashMap<String, Value> = HashMap::new();
+                // With case folding, entries are indexed by their folded key and
+                // `spellings` remembers the spelling each one keeps.
+                let mut spellings: HashMap<String, String> = HashMap::new();
+                let mut combined: HashSet<String> = HashSet::new();
+                for pair in pair.into_inner() {
+                    let mut inner_rules = pair.into_inner();
+                    let name = borrowed::unescape(
+                        inner_rules
                             .next()
                             .unwrap()
                             .into_inner()
                             .next()
                             .unwrap()
-                            .as_str()
-                            .to_string();
-                        let value = Self::json_parse_value_inner(inner_rules.next().unwrap());
-                        (name, value)
-                    })
-                    .collect::<HashMap<String, Value>>();
+                            .as_str(),
+                    )
+                    .into_owned();
+                    let value = Self::json_parse_value_inner(inner_rules.next().unwrap(), options)?;
+                    let (key, spelling) = if options.case_fold_keys {
+                        (name.to_lowercase(), Some(name))
+                    } else {
+                        (name, None)
+                    };
 
+                    match map.get_mut(&key) {
+                        None => {
+                            if let Some(spelling) = spelling {
+                                spellings.insert(key.clone(), spelling);
+                            }
+                            map.insert(key, value);
+                        }
+                        Some(existing) => match options.duplicates {
+                            DuplicateKeyPolicy::KeepLast => {
+                                if let Some(spelling) = spelling {
+                                    spellings.insert(key, spelling);
+                                }
+                                *existing = value;
+                            }
+                            DuplicateKeyPolicy::KeepFirst => {}
+                            DuplicateKeyPolicy::Error => {
+                                return Err(Error::NonParsebleMsg(format!(
+                                    "Duplicate key: {}",
+                                    spelling.unwrap_or(key)
+                                )))
+                            }
+                            DuplicateKeyPolicy::Combine => {
+                                if combined.contains(&key) {
+                                    existing.push(value);
+                                } else {
+                                    let first = std::mem::replace(existing, Value::Null);
+                                    *existing = Self::from(vec![first, value]);
+                                    combined.insert(key);
+                                }
+                            }
+                        },
+                    }
+                }
+
+                if options.case_fold_keys {
+                    map = map
+                        .into_iter()
+                        .map(|(key, value)| match spellings.remove(&key) {
+                            Some(spelling) => (spelling, value),
+                            None => (key, value),
+                        })
+                        .collect();
+                }
                 Self::from(map)
             }
             Rule::array => Self::from(
                 pair.into_inner()
-                    .map(Self::json_parse_value_inner)
-                    .collect::<Vec<_>>(),
+                    .map(|pair| Self::json_parse_value_inner(pair, options))
+                    .collect::<Result<Vec<_>, _>>()?,
             ),
-            Rule::string => Self::from(StringB::from(pair.into_inner().next().unwrap().as_str())),
+            Rule::string => Self::from(StringB::from(
+                borrowed::unescape(pair.into_inner().next().unwrap().as_str()).into_owned(),
+            )),
+            Rule::number if options.relaxed_numbers => relaxed_number(pair.as_str()),
             Rule::number => Self::from(Number::try_from(pair.as_str()).unwrap()),
             Rule::boolean => Self::Boolean(pair.as_str().parse().unwrap()),
             Rule::null => Self::Null,
@@ -58,8 +211,71 @@ impl Value {
             | Rule::inner
             | Rule::char
             | Rule::WHITESPACE => Self::Undefined,
-        }
+        };
+        Ok(value)
+    }
+}
+
+/// Reads an integer literal exactly, keeping it as a string when it overflows 128 bits.
+fn relaxed_number(raw: &str) -> Value {
+    if raw.contains(['.', 'e', 'E']) {
+        return Value::from(Number::try_from(raw).unwrap());
+    }
+    if let Ok(parsed) = raw.parse::<i32>() {
+        return Value::from(parsed);
     }
+    if let Ok(parsed) = raw.parse::<i64>() {
+        return Value::from(parsed);
+    }
+    if let Ok(parsed) = raw.parse::<u64>() {
+        return Value::from(parsed);
+    }
+    if let Ok(parsed) = raw.parse::<i128>() {
+        return Value::from(parsed);
+    }
+    if let Ok(parsed) = raw.parse::<u128>() {
+        return Value::from(parsed);
+    }
+    Value::from(raw)
+}
+
+/// Turns a pest failure into an error that quotes the offending line of `input`,
+/// with a caret under the column where parsing stopped.
+///
+/// `input` is the full document; the parsers only see it with surrounding
+/// whitespace trimmed, so the reported position is shifted back into place.
+fn parse_error(input: &str, error: &pest::error::Error<Rule>) -> Error {
+    let offset = match error.location {
+        pest::error::InputLocation::Pos(pos) => pos,
+        pest::error::InputLocation::Span((start, _)) => start,
+    };
+    let offset = input.len() - input.trim_start().len() + offset;
+    Error::NonParsebleMsg(render_context(input, offset, &error.variant.message()))
+}
+
+/// Renders `message` with the line holding the byte `offset` and a caret under it.
+fn render_context(input: &str, offset: usize, message: &str) -> String {
+    let offset = offset.min(input.len());
+    let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = input[offset..]
+        .find('\n')
+        .map_or(input.len(), |index| offset + index);
+    let line = input[line_start..line_end].trim_end_matches('\r');
+    let line_number = input[..line_start].matches('\n').count() + 1;
+    let column = input[line_start..offset].chars().count() + 1;
+    let gutter = " ".repeat(line_number.to_string().len());
+
+    format!(
+        "{} at line {}, column {}\n{} |\n{} | {}\n{} | {}^",
+        message,
+        line_number,
+        column,
+        gutter,
+        line_number,
+        line,
+        gutter,
+        " ".repeat(column - 1)
+    )
 }
 
 #[cfg(test)]
@@ -127,6 +343,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn string_escapes() {
+        let raw = r#"{"k\u00e9y": "say \"hi\"\n\u00e9\ud83d\ude80"}"#;
+        let value = Value::json_to_value(raw).unwrap();
+
+        assert_eq!(value.get("kéy"), Some(&"say \"hi\"\né🚀".to_value()));
+        assert_eq!(
+            super::borrowed::BorrowedValue::parse_json(raw)
+                .unwrap()
+                .to_value(),
+            value
+        );
+    }
+
     #[test]
     fn null() {
         let null = "null";
@@ -156,4 +386,117 @@ mod tests {
         assert_eq!(null, Value::Null);
         assert_eq!(string, "123".to_value());
     }
+
+    #[test]
+    fn tolerates_bom_and_surrounding_whitespace() {
+        let expected = Value::from(vec![("a", 1)]);
+
+        assert_eq!(
+            Value::json_to_value("\u{FEFF}{\"a\": 1}"),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            Value::json_to_value("\n\r\n\t  {\"a\": 1}\n\n"),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            Value::json_to_value("\u{FEFF}\n  {\"a\": 1}  "),
+            Ok(expected)
+        );
+        assert!(Value::json_to_value("{\"a\": 1}\u{FEFF}").is_err());
+    }
+
+    #[test]
+    fn reports_the_offending_line() {
+        let raw = "{\n  \"a\": 1,\n  \"b\" 2\n}";
+        let message = match Value::json_to_value(raw) {
+            Err(Error::NonParsebleMsg(message)) => message,
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        assert!(message.contains("at line 3, column 3"), "{}", message);
+        assert!(message.ends_with("3 |   \"b\" 2\n  |   ^"), "{}", message);
+
+        let message = Value::json_to_value("\n\n[1, 2,]").unwrap_err().to_string();
+        assert!(message.contains("3 | [1, 2,]\n  |       ^"), "{}", message);
+    }
+
+    #[test]
+    fn duplicate_key_policies() {
+        let raw = r#"{"a":1,"a":2,"a":3,"b":true}"#;
+
+        let last = Value::json_to_value_opts(raw, DuplicateKeyPolicy::KeepLast).unwrap();
+        assert_eq!(last.get("a"), Some(&3.to_value()));
+        assert_eq!(Value::json_to_value(raw), Ok(last));
+
+        let first = Value::json_to_value_opts(raw, DuplicateKeyPolicy::KeepFirst).unwrap();
+        assert_eq!(first.get("a"), Some(&1.to_value()));
+
+        assert_eq!(
+            Value::json_to_value_opts(raw, DuplicateKeyPolicy::Error),
+            Err(Error::NonParsebleMsg("Duplicate key: a".to_string()))
+        );
+
+        let combined = Value::json_to_value_opts(raw, DuplicateKeyPolicy::Combine).unwrap();
+        assert_eq!(combined.get("a"), Some(&Value::from(vec![1, 2, 3])));
+        assert_eq!(combined.get("b"), Some(&true.to_value()));
+
+        let nested = r#"{"x":{"a":[1],"a":[2]}}"#;
+        let combined = Value::json_to_value_opts(nested, DuplicateKeyPolicy::Combine).unwrap();
+        assert_eq!(
+            combined.pointer("/x/a"),
+            Some(&Value::from(vec![
+                Value::from(vec![1]),
+                Value::from(vec![2])
+            ]))
+        );
+        assert!(Value::json_to_value_opts(nested, DuplicateKeyPolicy::Error).is_err());
+    }
+
+    #[test]
+    fn case_folded_keys() {
+        let raw = r#"{"Name":1,"name":2}"#;
+
+        let folded =
+            Value::json_to_value_opts(raw, JsonParseOptions::new().case_fold_keys(true)).unwrap();
+        assert_eq!(folded.len(), 1);
+        assert_eq!(folded.get("name"), Some(&2.to_value()));
+
+        let first = Value::json_to_value_opts(
+            raw,
+            JsonParseOptions::new()
+                .duplicates(DuplicateKeyPolicy::KeepFirst)
+                .case_fold_keys(true),
+        )
+        .unwrap();
+        assert_eq!(first.get("Name"), Some(&1.to_value()));
+        assert!(Value::json_to_value_opts(
+            raw,
+            JsonParseOptions::new()
+                .duplicates(DuplicateKeyPolicy::Error)
+                .case_fold_keys(true)
+        )
+        .is_err());
+
+        let plain = Value::json_to_value_opts(raw, DuplicateKeyPolicy::KeepLast).unwrap();
+        assert_eq!(plain.len(), 2);
+    }
+
+    #[test]
+    fn relaxed_numbers_keep_big_integers() {
+        let big = "1234567890123456789012345678901234567890";
+        let raw = format!(
+            r#"{{"id": {}, "count": 3, "ratio": 0.5, "large": 9007199254740993}}"#,
+            big
+        );
+
+        let value = Value::json_to_value_relaxed_numbers(&raw).unwrap();
+        assert_eq!(value.get("id"), Some(&big.to_value()));
+        assert_eq!(value.get("count"), Some(&3.to_value()));
+        assert_eq!(value.get("ratio"), Some(&0.5.to_value()));
+        assert_eq!(value.get("large"), Some(&9007199254740993i64.to_value()));
+
+        let strict = Value::json_to_value(&raw).unwrap();
+        assert!(strict.get("id").unwrap().is_number());
+    }
 }
@@ -0,0 +1,146 @@
+//! Parsing that remembers where each node came from.
+//!
+//! `Value` carries no location data, so `Value::json_to_value_spanned` returns the
+//! parsed value next to a table of byte ranges keyed by JSON Pointer. Editors and
+//! linters can look a node up by the same pointer they use with `Value::pointer`
+//! and highlight exactly the text it was parsed from.
+use super::borrowed::unescape;
+use super::{parse_error, JSONParser, JsonParseOptions, Rule};
+use crate::impls::escape_pointer_token;
+use crate::prelude::*;
+use pest::iterators::Pair;
+use pest::Parser;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// A parsed JSON document together with the source span of every node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedValue {
+    value: Value,
+    spans: HashMap<String, Range<usize>>,
+}
+
+impl SpannedValue {
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+
+    /// Returns the byte range in the original input of the node at `pointer`, the root
+    /// being `""`. Ranges cover the whole node, including quotes and brackets.
+    pub fn span(&self, pointer: &str) -> Option<Range<usize>> {
+        self.spans.get(pointer).cloned()
+    }
+}
+
+impl Value {
+    /// Parses a JSON document like `json_to_value`, also recording the byte range each
+    /// node occupies in `str`. When a key repeats, the span of the kept (last) entry
+    /// is recorded.
+    ///
+    /// There is no `Value::span(&self)`: storing a range on every node would grow
+    /// `Value` for all users and be lost as soon as a node is cloned or moved. Spans
+    /// are instead looked up with `SpannedValue::span` by the node's JSON Pointer.
+    pub fn json_to_value_spanned(str: &str) -> Result<SpannedValue, Error> {
+        let body = str.strip_prefix('\u{FEFF}').unwrap_or(str);
+        let offset = str.len() - body.trim_start().len();
+        match JSONParser::parse(Rule::json, body.trim()) {
+            Ok(mut pairs) => match pairs.next() {
+                Some(pair) => {
+                    let mut spans = HashMap::new();
+                    record_spans(pair.clone(), &mut String::new(), offset, &mut spans);
+                    let value = Self::json_parse_value_inner(pair, JsonParseOptions::default())?;
+                    Ok(SpannedValue { value, spans })
+                }
+                None => Err(Error::NonParseble),
+            },
+            Err(msg) => Err(parse_error(body, &msg)),
+        }
+    }
+}
+
+fn record_spans(
+    pair: Pair<Rule>,
+    path: &mut String,
+    offset: usize,
+    spans: &mut HashMap<String, Range<usize>>,
+) {
+    let span = pair.as_span();
+    spans.insert(path.clone(), offset + span.start()..offset + span.end());
+
+    let len = path.len();
+    match pair.as_rule() {
+        Rule::object => {
+            let mut seen = HashSet::new();
+            for pair in pair.into_inner() {
+                let mut inner_rules = pair.into_inner();
+                let name = unescape(
+                    inner_rules
+                        .next()
+                        .unwrap()
+                        .into_inner()
+                        .next()
+                        .unwrap()
+                        .as_str(),
+                );
+                path.push('/');
+                path.push_str(&escape_pointer_token(&name));
+                if !seen.insert(name) {
+                    // The repeated key replaces the earlier entry; drop that entry's children.
+                    let prefix = format!("{}/", path);
+                    spans.retain(|pointer, _| !pointer.starts_with(&prefix));
+                }
+                record_spans(inner_rules.next().unwrap(), path, offset, spans);
+                path.truncate(len);
+            }
+        }
+        Rule::array => {
+            for (index, pair) in pair.into_inner().enumerate() {
+                path.push_str(&format!("/{}", index));
+                record_spans(pair, path, offset, spans);
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_node_spans() {
+        let raw = "\n  {\"name\": \"api\", \"ports\": [80, 443], \"a/b\": null}";
+        let spanned = Value::json_to_value_spanned(raw).unwrap();
+
+        assert_eq!(spanned.value(), &Value::json_to_value(raw).unwrap());
+        assert_eq!(spanned.span(""), Some(3..raw.len()));
+        assert_eq!(&raw[spanned.span("/name").unwrap()], "\"api\"");
+        assert_eq!(&raw[spanned.span("/ports").unwrap()], "[80, 443]");
+        assert_eq!(&raw[spanned.span("/ports/1").unwrap()], "443");
+        assert_eq!(&raw[spanned.span("/a~1b").unwrap()], "null");
+        assert_eq!(spanned.span("/missing"), None);
+    }
+
+    #[test]
+    fn repeated_keys_keep_only_the_last_entry_spans() {
+        let raw = r#"{"a":{"x":1},"a":2,"b":{"y":{"z":3}},"b":{"y":4}}"#;
+        let spanned = Value::json_to_value_spanned(raw).unwrap();
+
+        assert_eq!(&raw[spanned.span("/a").unwrap()], "2");
+        assert_eq!(spanned.span("/a/x"), None);
+        assert_eq!(&raw[spanned.span("/b/y").unwrap()], "4");
+        assert_eq!(spanned.span("/b/y/z"), None);
+    }
+
+    #[test]
+    fn spans_count_the_byte_order_mark() {
+        let raw = "\u{FEFF}[true]";
+        let spanned = Value::json_to_value_spanned(raw).unwrap();
+        assert_eq!(&raw[spanned.span("/0").unwrap()], "true");
+    }
+}
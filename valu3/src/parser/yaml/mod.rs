@@ -0,0 +1,417 @@
+use crate::parser::json::borrowed::unescape;
+use crate::prelude::*;
+use pest::Parser;
+use std::collections::HashMap;
+
+#[derive(Parser)]
+#[grammar = "parser/yaml/yaml.pest"]
+struct YAMLParser;
+
+use pest::iterators::Pair;
+
+/// Which plain scalars `Value::yaml_to_value_opts` reads as booleans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YamlBoolStyle {
+    /// Only `true` and `false`, as in `yaml_to_value`.
+    #[default]
+    Strict,
+    /// Also the YAML 1.1 spellings `yes`/`no`/`on`/`off`, and `True`/`False`, in
+    /// lowercase, capitalized or uppercase form.
+    Permissive,
+}
+
+impl Value {
+    /// Parses a YAML document written in flow style, e.g. `{a: 1, b: [1, 2]}`, or in
+    /// block style, with `key: value` lines and `- ` items nested by indentation.
+    ///
+    /// `null`, `Null`, `NULL`, `~` and a key with no value (`a:` or `{a: }`) all parse as
+    /// `Null`.
+    ///
+    /// Tokens the grammar accepts but that do not make a usable value, such as a number
+    /// too large for any numeric type, are reported as errors.
+    pub fn yaml_to_value(str: &str) -> Result<Value, Error> {
+        Self::yaml_to_value_opts(str, YamlBoolStyle::Strict)
+    }
+
+    pub fn yaml_to_value_opts(str: &str, bool_style: YamlBoolStyle) -> Result<Value, Error> {
+        // Only the end is trimmed, so the columns of block-style lines stay intact.
+        match YAMLParser::parse(Rule::yaml, str.trim_end()) {
+            Ok(mut pairs) => match pairs.next() {
+                Some(pair) => Self::yaml_parse_value_inner(pair, bool_style),
+                None => Err(Error::NonParseble),
+            },
+            Err(msg) => Err(Error::NonParsebleMsg(msg.to_string())),
+        }
+    }
+
+    fn yaml_parse_value_inner(pair: Pair<Rule>, bool_style: YamlBoolStyle) -> Result<Self, Error> {
+        let value = match pair.as_rule() {
+            Rule::mapping => {
+                let mut map = HashMap::new();
+                for pair in pair.into_inner() {
+                    let mut inner_rules = pair.into_inner();
+                    let name = yaml_key(yaml_next(&mut inner_rules)?)?;
+                    let value = match inner_rules.next() {
+                        Some(pair) => Self::yaml_parse_value_inner(pair, bool_style)?,
+                        None => Self::Null,
+                    };
+                    map.insert(name, value);
+                }
+
+                Self::from(map)
+            }
+            Rule::block => yaml_block(pair, bool_style)?,
+            Rule::sequence => Self::from(
+                pair.into_inner()
+                    .map(|pair| Self::yaml_parse_value_inner(pair, bool_style))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            Rule::double_quoted => {
+                let inner = yaml_next(&mut pair.into_inner())?.as_str();
+                Self::from(StringB::from(unescape(inner).into_owned()))
+            }
+            Rule::single_quoted => {
+                let inner = yaml_next(&mut pair.into_inner())?.as_str();
+                Self::from(StringB::from(inner.replace("''", "'")))
+            }
+            Rule::plain | Rule::block_plain => match (bool_style, yaml_loose_bool(pair.as_str())) {
+                (YamlBoolStyle::Permissive, Some(boolean)) => Self::Boolean(boolean),
+                _ => Self::from(StringB::from(pair.as_str())),
+            },
+            Rule::number => Self::Number(yaml_number(pair.as_str())?),
+            Rule::boolean => Self::Boolean(pair.as_str().parse().map_err(|_| {
+                Error::NonParsebleMsg(format!("Invalid boolean: {}", pair.as_str()))
+            })?),
+            Rule::null => Self::Null,
+            Rule::yaml
+            | Rule::EOI
+            | Rule::pair
+            | Rule::value
+            | Rule::key
+            | Rule::end
+            | Rule::dq_inner
+            | Rule::sq_inner
+            | Rule::plain_start
+            | Rule::plain_char
+            | Rule::block_line
+            | Rule::block_marker
+            | Rule::block_item
+            | Rule::block_key
+            | Rule::block_value
+            | Rule::block_scalar
+            | Rule::block_end
+            | Rule::block_break
+            | Rule::block_plain_char
+            | Rule::WHITESPACE
+            | Rule::COMMENT => Self::Undefined,
+        };
+        Ok(value)
+    }
+}
+
+fn yaml_next<'a>(pairs: &mut pest::iterators::Pairs<'a, Rule>) -> Result<Pair<'a, Rule>, Error> {
+    pairs.next().ok_or(Error::NonParseble)
+}
+
+/// Reads a mapping key. Keys keep their spelling: `on:` is a key named "on", not `true`.
+fn yaml_key(pair: Pair<Rule>) -> Result<String, Error> {
+    Ok(
+        match Value::yaml_parse_value_inner(pair, YamlBoolStyle::Strict)? {
+            Value::String(name) => name.to_string(),
+            other => other.to_string(),
+        },
+    )
+}
+
+/// A `- ` marker, a `key:` marker or a value in a block-style document.
+struct BlockToken {
+    offset: usize,
+    line_start: usize,
+    column: usize,
+    kind: BlockKind,
+}
+
+enum BlockKind {
+    Item,
+    Key(String),
+    Value(Value),
+}
+
+type BlockTokens = std::iter::Peekable<std::vec::IntoIter<BlockToken>>;
+
+/// Builds a block-style document. The grammar yields its markers and values in order;
+/// how they nest follows from the column each one starts at, as in YAML itself.
+fn yaml_block(pair: Pair<Rule>, bool_style: YamlBoolStyle) -> Result<Value, Error> {
+    let input = pair.get_input();
+    let mut tokens = Vec::new();
+    for pair in pair.into_inner() {
+        let offset = pair.as_span().start();
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let kind = match pair.as_rule() {
+            Rule::block_item => BlockKind::Item,
+            Rule::block_key => BlockKind::Key(yaml_key(yaml_next(&mut pair.into_inner())?)?),
+            _ => BlockKind::Value(Value::yaml_parse_value_inner(pair, bool_style)?),
+        };
+        tokens.push(BlockToken {
+            offset,
+            line_start,
+            column: input[line_start..offset].chars().count(),
+            kind,
+        });
+    }
+
+    let mut tokens = tokens.into_iter().peekable();
+    let value = yaml_block_node(&mut tokens)?;
+    match tokens.next() {
+        None => Ok(value),
+        Some(token) => Err(Error::NonParsebleMsg(format!(
+            "Unexpected content at line {}",
+            input[..token.offset].matches('\n').count() + 1
+        ))),
+    }
+}
+
+/// Builds the node starting at the next token: a sequence of the `- ` markers in its
+/// column, a mapping of the `key:` markers in its column, or a single value.
+fn yaml_block_node(tokens: &mut BlockTokens) -> Result<Value, Error> {
+    if let Some(token) = tokens.next_if(|token| matches!(token.kind, BlockKind::Value(_))) {
+        if let BlockKind::Value(value) = token.kind {
+            return Ok(value);
+        }
+    }
+
+    let (column, is_item) = match tokens.peek() {
+        Some(token) => (token.column, matches!(token.kind, BlockKind::Item)),
+        None => return Ok(Value::Null),
+    };
+
+    if is_item {
+        let mut items = Vec::new();
+        while let Some(marker) =
+            tokens.next_if(|token| token.column == column && matches!(token.kind, BlockKind::Item))
+        {
+            items.push(yaml_block_child(tokens, &marker)?);
+        }
+        return Ok(Value::from(items));
+    }
+
+    let mut map = HashMap::new();
+    while let Some(marker) =
+        tokens.next_if(|token| token.column == column && matches!(token.kind, BlockKind::Key(_)))
+    {
+        let value = yaml_block_child(tokens, &marker)?;
+        if let BlockKind::Key(name) = marker.kind {
+            map.insert(name, value);
+        }
+    }
+    Ok(Value::from(map))
+}
+
+/// Builds the value of a marker: what follows it on the same line, or the lines indented
+/// below it. A sequence under a key may also sit in the key's own column. A marker with
+/// neither is `Null`.
+fn yaml_block_child(tokens: &mut BlockTokens, marker: &BlockToken) -> Result<Value, Error> {
+    let nested = match tokens.peek() {
+        Some(next) => {
+            next.line_start == marker.line_start
+                || next.column > marker.column
+                || (matches!(marker.kind, BlockKind::Key(_))
+                    && matches!(next.kind, BlockKind::Item)
+                    && next.column == marker.column)
+        }
+        None => false,
+    };
+    if nested {
+        yaml_block_node(tokens)
+    } else {
+        Ok(Value::Null)
+    }
+}
+
+/// Reads the YAML 1.1 boolean spellings that the grammar leaves as plain scalars.
+fn yaml_loose_bool(token: &str) -> Option<bool> {
+    match token {
+        "yes" | "Yes" | "YES" | "on" | "On" | "ON" | "True" | "TRUE" => Some(true),
+        "no" | "No" | "NO" | "off" | "Off" | "OFF" | "False" | "FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+/// Converts a number token, rejecting values that overflow to infinity (e.g. `1e999`).
+fn yaml_number(token: &str) -> Result<Number, Error> {
+    let invalid = || Error::NonParsebleMsg(format!("Invalid number: {}", token));
+    let number = Number::try_from(token).map_err(|_| invalid())?;
+    match number.to_f64() {
+        Some(float) if float.is_finite() => Ok(number),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn flow_mapping_matches_json() {
+        let yaml = Value::yaml_to_value("{a: 1, b: [1, 2]}").unwrap();
+        let json = Value::json_to_value(r#"{"a": 1, "b": [1, 2]}"#).unwrap();
+
+        assert_eq!(yaml, json);
+    }
+
+    #[test]
+    fn flow_sequence_of_scalars() {
+        let value = Value::yaml_to_value("[1, -2.5, true, null, plain text, 'it''s', \"a\\tb\"]");
+
+        assert_eq!(
+            value,
+            Ok(Value::from(vec![
+                Value::from(1),
+                Value::from(-2.5),
+                Value::from(true),
+                Value::Null,
+                Value::from("plain text"),
+                Value::from("it's"),
+                Value::from("a\tb"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn nested_flow_collections() {
+        let value = Value::yaml_to_value(
+            "{ server: {host: localhost, ports: [80, 443]}, tags: [], meta: {}, url: http://x.io/a }",
+        )
+        .unwrap();
+
+        assert_eq!(value.pointer("/server/host"), Some(&"localhost".to_value()));
+        assert_eq!(
+            value.pointer("/server/ports"),
+            Some(&Value::from(vec![80, 443]))
+        );
+        assert_eq!(
+            value.pointer("/tags"),
+            Some(&Value::from(Vec::<Value>::new()))
+        );
+        assert!(value.pointer("/meta").unwrap().is_object());
+        assert_eq!(value.pointer("/url"), Some(&"http://x.io/a".to_value()));
+    }
+
+    #[test]
+    fn plain_scalars_that_look_like_numbers() {
+        assert_eq!(Value::yaml_to_value("12abc"), Ok(Value::from("12abc")));
+        assert_eq!(
+            Value::yaml_to_value("nullable"),
+            Ok(Value::from("nullable"))
+        );
+        assert!(Value::yaml_to_value("{a: [1, 2}").is_err());
+    }
+
+    #[test]
+    fn block_style_documents() {
+        let value = Value::yaml_to_value(
+            "# service\n\
+             server:\n  host: localhost  # default\n\n  ports: [80, 443]\n\
+             greeting: hello, world\n\
+             \"quoted key\": 'v'\n\
+             steps:\n- name: build\n  args:\n    - - -v\n      - -q\n- run\n",
+        )
+        .unwrap();
+
+        assert_eq!(value.pointer("/server/host"), Some(&"localhost".to_value()));
+        assert_eq!(
+            value.pointer("/server/ports"),
+            Some(&Value::from(vec![80, 443]))
+        );
+        assert_eq!(value.get("greeting"), Some(&"hello, world".to_value()));
+        assert_eq!(value.get("quoted key"), Some(&"v".to_value()));
+        assert_eq!(value.pointer("/steps/0/name"), Some(&"build".to_value()));
+        assert_eq!(
+            value.pointer("/steps/0/args/0"),
+            Some(&Value::from(vec!["-v", "-q"]))
+        );
+        assert_eq!(value.pointer("/steps/1"), Some(&"run".to_value()));
+
+        assert_eq!(
+            Value::yaml_to_value("- - - 1\n  - 2\n"),
+            Ok(Value::from(vec![Value::from(vec![
+                Value::from(vec![1]),
+                Value::from(2)
+            ])]))
+        );
+        assert!(Value::yaml_to_value("a:\n  b: 1\n c: 2").is_err());
+    }
+
+    #[test]
+    fn null_spellings() {
+        for raw in ["key: ~", "key:", "key: NULL"] {
+            let value = Value::yaml_to_value(raw).unwrap();
+            assert_eq!(value.get("key"), Some(&Value::Null), "{}", raw);
+        }
+
+        let value = Value::yaml_to_value("{a: ~, b:, c: NULL, d: Null, e: }").unwrap();
+        for key in ["a", "b", "c", "d", "e"] {
+            assert_eq!(value.get(key), Some(&Value::Null), "key {}", key);
+        }
+
+        assert_eq!(
+            Value::yaml_to_value("[~, NULL, ~x]"),
+            Ok(Value::from(vec![
+                Value::Null,
+                Value::Null,
+                Value::from("~x")
+            ]))
+        );
+        assert_eq!(Value::yaml_to_value("nULL"), Ok(Value::from("nULL")));
+    }
+
+    #[test]
+    fn permissive_booleans() {
+        let raw = "{enabled: yes, debug: Off, strict: True, name: yesterday}";
+
+        let value = Value::yaml_to_value_opts(raw, YamlBoolStyle::Permissive).unwrap();
+        assert_eq!(value.get("enabled"), Some(&Value::Boolean(true)));
+        assert_eq!(value.get("debug"), Some(&Value::Boolean(false)));
+        assert_eq!(value.get("strict"), Some(&Value::Boolean(true)));
+        assert_eq!(value.get("name"), Some(&"yesterday".to_value()));
+
+        let value = Value::yaml_to_value(raw).unwrap();
+        assert_eq!(value.get("enabled"), Some(&"yes".to_value()));
+        assert_eq!(value.get("strict"), Some(&"True".to_value()));
+        assert_eq!(
+            Value::yaml_to_value_opts(raw, YamlBoolStyle::Strict),
+            Ok(value)
+        );
+
+        let value = Value::yaml_to_value_opts("enabled: yes", YamlBoolStyle::Permissive).unwrap();
+        assert_eq!(value.get("enabled"), Some(&Value::Boolean(true)));
+        let value = Value::yaml_to_value("enabled: yes").unwrap();
+        assert_eq!(value.get("enabled"), Some(&"yes".to_value()));
+    }
+
+    #[test]
+    fn permissive_booleans_leave_keys_alone() {
+        let value =
+            Value::yaml_to_value_opts("{on: push, yes: 1, off: no}", YamlBoolStyle::Permissive)
+                .unwrap();
+
+        assert_eq!(value.get("on"), Some(&"push".to_value()));
+        assert_eq!(value.get("yes"), Some(&1.to_value()));
+        assert_eq!(value.get("off"), Some(&Value::Boolean(false)));
+        assert_eq!(value.get("true"), None);
+
+        let value =
+            Value::yaml_to_value_opts("on:\n  - push\n", YamlBoolStyle::Permissive).unwrap();
+        assert_eq!(value.get("on"), Some(&Value::from(vec!["push"])));
+    }
+
+    #[test]
+    fn out_of_range_numbers_are_errors() {
+        assert_eq!(
+            Value::yaml_to_value("1e999"),
+            Err(Error::NonParsebleMsg("Invalid number: 1e999".to_string()))
+        );
+        assert!(Value::yaml_to_value("{a: [1, -1e400]}").is_err());
+        assert_eq!(Value::yaml_to_value("1e300"), Ok(Value::from(1e300)));
+    }
+}
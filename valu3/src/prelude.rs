@@ -4,11 +4,16 @@ pub use crate::types::number::*;
 pub use crate::types::array::*;
 pub use crate::types::object::*;
 pub use crate::types::datetime::*;
+pub use crate::types::kind::*;
 pub use crate::types::value_key::*;
+pub use crate::types::frozen::*;
+#[cfg(feature = "shared")]
+pub use crate::types::shared::*;
 pub use crate::traits::*;
 pub use crate::to_value::*;
 pub use crate::to::json::*;
 pub use crate::to::yaml::*;
+pub use crate::to::pretty::*;
 pub use crate::value::*;
 pub use crate::Error;
 pub use crate::impls::*;
@@ -18,3 +23,13 @@ pub use std::ffi::CString;
 pub use valu3_derive::*;
 #[cfg(feature = "serde")]
 pub use crate::serde_value::*;
+#[cfg(feature = "parser")]
+pub use crate::parser::json::borrowed::*;
+#[cfg(feature = "parser")]
+pub use crate::parser::json::events::*;
+#[cfg(feature = "parser")]
+pub use crate::parser::json::spanned::*;
+#[cfg(feature = "parser")]
+pub use crate::parser::json::{DuplicateKeyPolicy, JsonParseOptions};
+#[cfg(feature = "parser")]
+pub use crate::parser::yaml::YamlBoolStyle;
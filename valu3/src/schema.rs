@@ -0,0 +1,234 @@
+use crate::prelude::*;
+use std::collections::BTreeSet;
+
+impl Value {
+    /// Infers a JSON-Schema skeleton describing the structure of this sample value.
+    ///
+    /// Objects produce `properties` and a sorted `required` list, arrays produce `items`
+    /// (an `anyOf` union when elements differ), and scalars produce their `type`. Integers are
+    /// reported as `integer`, other numbers as `number`, and date-times as strings with a `format`.
+    pub fn infer_schema(&self) -> Value {
+        match self {
+            Value::Null | Value::Undefined => schema_type("null"),
+            Value::Boolean(_) => schema_type("boolean"),
+            Value::Number(number) if number.is_float() => schema_type("number"),
+            Value::Number(_) => schema_type("integer"),
+            Value::String(_) => schema_type("string"),
+            Value::DateTime(datetime) => {
+                let format = match datetime {
+                    DateTime::Date(_) => "date",
+                    DateTime::Time(_) => "time",
+                    DateTime::DateTime(_) => "date-time",
+                };
+                let mut schema = schema_type("string");
+                schema.insert("format", Value::from(format));
+                schema
+            }
+            Value::Array(array) => {
+                let mut items: Vec<Value> = Vec::new();
+                for item in array {
+                    let item = item.infer_schema();
+                    if !items.contains(&item) {
+                        items.push(item);
+                    }
+                }
+
+                let mut schema = schema_type("array");
+                match items.len() {
+                    0 => {}
+                    1 => {
+                        schema.insert("items", items.remove(0));
+                    }
+                    _ => {
+                        schema.insert("items", Value::from(vec![("anyOf", Value::from(items))]));
+                    }
+                }
+                schema
+            }
+            Value::Object(object) => {
+                let mut properties = Object::default();
+                let mut required = Vec::with_capacity(object.len());
+                for (key, value) in object.iter() {
+                    properties.insert(key.to_string(), value.infer_schema());
+                    required.push(key.to_string());
+                }
+                required.sort();
+
+                let mut schema = schema_type("object");
+                schema.insert("properties", properties.to_value());
+                schema.insert("required", Value::from(required));
+                schema
+            }
+        }
+    }
+
+    /// Infers one schema that describes every sample in `values`.
+    ///
+    /// Samples of different types produce an `anyOf` union. Objects are merged: a property
+    /// seen in any sample is described by the union of its values, and only properties
+    /// present in every sample are listed as `required`. Array elements are merged across
+    /// all arrays the same way. An empty slice yields the empty schema `{}`.
+    pub fn infer_schema_from(values: &[Value]) -> Value {
+        schema_from(&values.iter().collect::<Vec<_>>())
+    }
+}
+
+fn schema_from(values: &[&Value]) -> Value {
+    let objects: Vec<&Object> = values
+        .iter()
+        .filter_map(|value| value.as_object())
+        .collect();
+    let arrays: Vec<&Array> = values.iter().filter_map(|value| value.as_array()).collect();
+
+    let mut variants: Vec<Value> = Vec::new();
+    let (mut seen_object, mut seen_array) = (false, false);
+    for value in values {
+        let schema = match value {
+            Value::Object(_) if !seen_object => {
+                seen_object = true;
+                object_schema_from(&objects)
+            }
+            Value::Array(_) if !seen_array => {
+                seen_array = true;
+                array_schema_from(&arrays)
+            }
+            Value::Object(_) | Value::Array(_) => continue,
+            scalar => scalar.infer_schema(),
+        };
+        if !variants.contains(&schema) {
+            variants.push(schema);
+        }
+    }
+
+    match variants.len() {
+        0 => Object::default().to_value(),
+        1 => variants.remove(0),
+        _ => Value::from(vec![("anyOf", Value::from(variants))]),
+    }
+}
+
+fn object_schema_from(objects: &[&Object]) -> Value {
+    let keys: BTreeSet<String> = objects
+        .iter()
+        .flat_map(|object| object.keys())
+        .map(|key| key.to_string())
+        .collect();
+
+    let mut properties = Object::default();
+    let mut required = Vec::new();
+    for key in keys {
+        let present: Vec<&Value> = objects
+            .iter()
+            .filter_map(|object| object.get(key.as_str()))
+            .collect();
+        if present.len() == objects.len() {
+            required.push(key.clone());
+        }
+        properties.insert(key, schema_from(&present));
+    }
+
+    let mut schema = schema_type("object");
+    schema.insert("properties", properties.to_value());
+    schema.insert("required", Value::from(required));
+    schema
+}
+
+fn array_schema_from(arrays: &[&Array]) -> Value {
+    let items: Vec<&Value> = arrays.iter().flat_map(|array| array.into_iter()).collect();
+    let mut schema = schema_type("array");
+    if !items.is_empty() {
+        schema.insert("items", schema_from(&items));
+    }
+    schema
+}
+
+fn schema_type(name: &str) -> Value {
+    Value::from(vec![("type", name)])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_infer_schema_from_user() {
+        let user = json!({
+            "id": 1,
+            "name": "Ana",
+            "score": 9.5,
+            "active": true,
+            "manager": null,
+            "tags": ["admin", "ops"],
+            "address": { "city": "Lisbon" }
+        });
+
+        let schema = user.infer_schema();
+        let type_of = |pointer: &str| schema.pointer(pointer).unwrap().to_string();
+
+        assert_eq!(type_of("/type"), "object");
+        assert_eq!(type_of("/properties/id/type"), "integer");
+        assert_eq!(type_of("/properties/name/type"), "string");
+        assert_eq!(type_of("/properties/score/type"), "number");
+        assert_eq!(type_of("/properties/active/type"), "boolean");
+        assert_eq!(type_of("/properties/manager/type"), "null");
+        assert_eq!(type_of("/properties/tags/type"), "array");
+        assert_eq!(type_of("/properties/tags/items/type"), "string");
+        assert_eq!(
+            type_of("/properties/address/properties/city/type"),
+            "string"
+        );
+        assert_eq!(
+            schema.pointer("/required"),
+            Some(&Value::from(vec![
+                "active", "address", "id", "manager", "name", "score", "tags"
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_infer_schema_array_union() {
+        let schema = json!([1, "a", 2, []]).infer_schema();
+        let any_of = schema.pointer("/items/anyOf").unwrap();
+
+        assert_eq!(any_of.pointer("/0/type"), Some(&"integer".to_value()));
+        assert_eq!(any_of.pointer("/1/type"), Some(&"string".to_value()));
+        assert_eq!(any_of.pointer("/2/type"), Some(&"array".to_value()));
+        assert_eq!(any_of.pointer("/3"), None);
+        assert_eq!(json!([]).infer_schema(), json!({ "type": "array" }));
+    }
+
+    #[test]
+    fn test_infer_schema_from_many() {
+        let records = vec![
+            json!({ "id": 1, "code": 10, "email": "a@x.io", "tags": ["a"] }),
+            json!({ "id": 2, "code": "B-20", "tags": [] }),
+            json!({ "id": 3, "code": 30, "tags": [1] }),
+        ];
+        let schema = Value::infer_schema_from(&records);
+
+        assert_eq!(schema.pointer("/type"), Some(&"object".to_value()));
+        assert_eq!(
+            schema.pointer("/required"),
+            Some(&Value::from(vec!["code", "id", "tags"]))
+        );
+        assert_eq!(
+            schema.pointer("/properties/email"),
+            Some(&json!({ "type": "string" }))
+        );
+        assert_eq!(
+            schema.pointer("/properties/code"),
+            Some(&json!({ "anyOf": [{ "type": "integer" }, { "type": "string" }] }))
+        );
+        assert_eq!(
+            schema.pointer("/properties/tags/items/anyOf/1"),
+            Some(&json!({ "type": "integer" }))
+        );
+
+        assert_eq!(Value::infer_schema_from(&[]), json!({}));
+        assert_eq!(
+            Value::infer_schema_from(&[json!(1), json!(2)]),
+            json!({ "type": "integer" })
+        );
+    }
+}
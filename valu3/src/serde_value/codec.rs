@@ -0,0 +1,111 @@
+//! Options for encoding `Value`s whose distinctions serde formats cannot express natively.
+//!
+//! `Value::Undefined` serializes as `null` through serde, so a round-trip turns it into
+//! `Value::Null`. With `preserve_undefined` enabled, `encode` replaces every `Undefined`
+//! with the sentinel object `{"$undefined": true}` before serialization and `decode`
+//! turns the sentinel back into `Undefined` after deserialization.
+use crate::prelude::*;
+
+/// Key of the sentinel object used to represent `Value::Undefined`.
+pub const UNDEFINED_SENTINEL_KEY: &str = "$undefined";
+
+/// Options controlling how a `Value` is encoded before serialization and decoded after.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ValueCodecOptions {
+    pub preserve_undefined: bool,
+}
+
+impl ValueCodecOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn preserve_undefined(mut self, preserve_undefined: bool) -> Self {
+        self.preserve_undefined = preserve_undefined;
+        self
+    }
+
+    /// Returns a copy of `value` ready to be serialized with any serde format.
+    pub fn encode(&self, value: &Value) -> Value {
+        let mut value = value.clone();
+        self.encode_in_place(&mut value);
+        value
+    }
+
+    /// Reverses `encode` on a freshly deserialized value.
+    pub fn decode(&self, mut value: Value) -> Value {
+        self.decode_in_place(&mut value);
+        value
+    }
+
+    fn encode_in_place(&self, value: &mut Value) {
+        match value {
+            Value::Undefined if self.preserve_undefined => {
+                *value = Value::from(vec![(UNDEFINED_SENTINEL_KEY, true)]);
+            }
+            _ => Self::for_each_child(value, |child| self.encode_in_place(child)),
+        }
+    }
+
+    fn decode_in_place(&self, value: &mut Value) {
+        match value {
+            Value::Object(object) if self.preserve_undefined && Self::is_sentinel(object) => {
+                *value = Value::Undefined;
+            }
+            _ => Self::for_each_child(value, |child| self.decode_in_place(child)),
+        }
+    }
+
+    fn for_each_child<F>(value: &mut Value, f: F)
+    where
+        F: FnMut(&mut Value),
+    {
+        match value {
+            Value::Array(array) => array.into_iter().for_each(f),
+            Value::Object(Object::BTreeMap(map)) => map.values_mut().for_each(f),
+            Value::Object(Object::HashMap(map)) => map.values_mut().for_each(f),
+            _ => {}
+        }
+    }
+
+    fn is_sentinel(object: &Object) -> bool {
+        object.len() == 1 && object.get(UNDEFINED_SENTINEL_KEY) == Some(&Value::Boolean(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_preserves_undefined() {
+        let value = Value::from(vec![
+            ("missing", Value::Undefined),
+            ("empty", Value::Null),
+            (
+                "list",
+                Value::from(vec![Value::Undefined, Value::from(1u64)]),
+            ),
+        ]);
+
+        let options = ValueCodecOptions::new().preserve_undefined(true);
+        let json = serde_json::to_string(&options.encode(&value)).unwrap();
+        let parsed: Value = serde_json::from_str(&json).unwrap();
+        let decoded = options.decode(parsed);
+
+        assert_eq!(decoded, value);
+        assert_eq!(decoded.get("missing"), Some(&Value::Undefined));
+        assert_eq!(decoded.get("empty"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_default_collapses_undefined_to_null() {
+        let value = Value::from(vec![("missing", Value::Undefined)]);
+
+        let options = ValueCodecOptions::default();
+        let json = serde_json::to_string(&options.encode(&value)).unwrap();
+        let parsed: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(options.decode(parsed).get("missing"), Some(&Value::Null));
+    }
+}
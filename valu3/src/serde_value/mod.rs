@@ -1,20 +1,81 @@
+pub mod codec;
 pub mod de;
 pub mod ser;
 
+pub use codec::{ValueCodecOptions, UNDEFINED_SENTINEL_KEY};
+
 use crate::prelude::*;
+use crate::to::base64::{base64_decode, base64_encode};
 use serde::de::{DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor};
 use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 use serde::Deserializer;
 use std::collections::HashMap;
 use std::fmt;
 
+/// Key of the single-entry object that marks serialized bytes, e.g. `{"$bytes": "AQID"}`.
+/// The payload is standard base64, so bytes stay distinct from ordinary number arrays.
+pub const BYTES_MARKER_KEY: &str = "$bytes";
+
+/// Returns the decoded payload when `value` is a bytes marker object.
+pub(crate) fn bytes_marker_payload(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Object(object) if object.len() == 1 => match object.get(BYTES_MARKER_KEY) {
+            Some(Value::String(encoded)) => base64_decode(encoded.as_str()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The category of a `SerdeValueError`, for handling errors without parsing the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerdeValueErrorKind {
+    /// Any error without a more specific kind, such as messages from `Error::custom`.
+    Custom,
+    /// A value had a different type than the target expected.
+    InvalidType {
+        expected: String,
+        found: String,
+    },
+    /// A number did not fit in the target type.
+    OutOfRange,
+    MissingField(String),
+    UnknownVariant(String),
+}
+
 /// Error type used when converting between `Value` and serde types.
 #[derive(Debug)]
-pub struct SerdeValueError(pub String);
+pub struct SerdeValueError {
+    pub kind: SerdeValueErrorKind,
+    pub message: String,
+}
+
+impl SerdeValueError {
+    pub fn new(kind: SerdeValueErrorKind, message: impl Into<String>) -> Self {
+        SerdeValueError {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn from_message(message: impl Into<String>) -> Self {
+        Self::new(SerdeValueErrorKind::Custom, message)
+    }
+
+    fn invalid_type_of(expected: &str, found: &str, message: impl Into<String>) -> Self {
+        Self::new(
+            SerdeValueErrorKind::InvalidType {
+                expected: expected.to_string(),
+                found: found.to_string(),
+            },
+            message,
+        )
+    }
+}
 
 impl std::fmt::Display for SerdeValueError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "SerdeValueError: {}", self.0)
+        write!(f, "SerdeValueError: {}", self.message)
     }
 }
 
@@ -22,13 +83,64 @@ impl std::error::Error for SerdeValueError {}
 
 impl serde::ser::Error for SerdeValueError {
     fn custom<T: fmt::Display>(msg: T) -> Self {
-        SerdeValueError(msg.to_string())
+        SerdeValueError::from_message(msg.to_string())
+    }
+}
+
+/// Carries serde's default wording for the typed errors below, so overriding them to
+/// record a kind keeps the messages unchanged.
+#[derive(Debug)]
+struct DefaultMessage(String);
+
+impl fmt::Display for DefaultMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DefaultMessage {}
+
+impl serde::de::Error for DefaultMessage {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        DefaultMessage(msg.to_string())
     }
 }
 
 impl serde::de::Error for SerdeValueError {
     fn custom<T: fmt::Display>(msg: T) -> Self {
-        SerdeValueError(msg.to_string())
+        SerdeValueError::from_message(msg.to_string())
+    }
+
+    fn invalid_type(unexp: serde::de::Unexpected, exp: &dyn serde::de::Expected) -> Self {
+        let message = DefaultMessage::invalid_type(unexp, exp).0;
+        Self::invalid_type_of(&exp.to_string(), &unexp.to_string(), message)
+    }
+
+    fn invalid_value(unexp: serde::de::Unexpected, exp: &dyn serde::de::Expected) -> Self {
+        use serde::de::Unexpected;
+
+        // Primitive visitors report numbers that do not fit their type as invalid values.
+        let kind = match unexp {
+            Unexpected::Signed(_) | Unexpected::Unsigned(_) | Unexpected::Float(_) => {
+                SerdeValueErrorKind::OutOfRange
+            }
+            _ => SerdeValueErrorKind::Custom,
+        };
+        Self::new(kind, DefaultMessage::invalid_value(unexp, exp).0)
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self::new(
+            SerdeValueErrorKind::MissingField(field.to_string()),
+            DefaultMessage::missing_field(field).0,
+        )
+    }
+
+    fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+        Self::new(
+            SerdeValueErrorKind::UnknownVariant(variant.to_string()),
+            DefaultMessage::unknown_variant(variant, expected).0,
+        )
     }
 }
 
@@ -68,16 +180,23 @@ impl SerializeMap for MapCollector {
     where
         T: Serialize,
     {
-        // serialize key into a Value and expect it to be a string
+        // serialize key into a Value; like serde_json, scalar keys are stringified
         let kv = key.serialize(ValueSerializer)?;
-        match kv {
-            Value::String(s) => {
-                // temporarily push with empty value; value filled in serialize_value
-                self.entries.push((s.to_string(), Value::Null));
-                Ok(())
+        let key = match kv {
+            Value::String(s) => s.to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::Boolean(b) => b.to_string(),
+            other => {
+                return Err(SerdeValueError::invalid_type_of(
+                    "string",
+                    other.type_name(),
+                    "map key must be a string",
+                ))
             }
-            _ => Err(SerdeValueError("map key must be a string".to_string())),
-        }
+        };
+        // temporarily push with empty value; value filled in serialize_value
+        self.entries.push((key, Value::Null));
+        Ok(())
     }
 
     fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
@@ -89,18 +208,18 @@ impl SerializeMap for MapCollector {
             *slot = v;
             Ok(())
         } else {
-            Err(SerdeValueError(
-                "serialize_value called before serialize_key".to_string(),
+            Err(SerdeValueError::from_message(
+                "serialize_value called before serialize_key",
             ))
         }
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut map: HashMap<String, Value> = HashMap::with_capacity(self.entries.len());
+        let mut object = Object::with_capacity(self.entries.len());
         for (k, v) in self.entries.into_iter() {
-            map.insert(k, v);
+            object.insert(k, v);
         }
-        Ok(Object::from(map).to_value())
+        Ok(object.to_value())
     }
 }
 
@@ -166,8 +285,8 @@ impl Serializer for ValueSerializer {
         Ok(StringB::from(v).to_value())
     }
 
-    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Err(SerdeValueError("bytes not supported".to_string()))
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::from(vec![(BYTES_MARKER_KEY, base64_encode(v))]))
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -259,19 +378,19 @@ impl Serializer for ValueSerializer {
         })
     }
 
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         Ok(MapCollector {
-            entries: Vec::new(),
+            entries: Vec::with_capacity(len.unwrap_or(0)),
         })
     }
 
     fn serialize_struct(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
         Ok(MapCollector {
-            entries: Vec::new(),
+            entries: Vec::with_capacity(len),
         })
     }
 
@@ -401,11 +520,11 @@ impl serde::ser::SerializeStruct for MapCollector {
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut map: HashMap<String, Value> = HashMap::with_capacity(self.entries.len());
+        let mut object = Object::with_capacity(self.entries.len());
         for (k, v) in self.entries.into_iter() {
-            map.insert(k, v);
+            object.insert(k, v);
         }
-        Ok(Object::from(map).to_value())
+        Ok(object.to_value())
     }
 }
 
@@ -444,7 +563,7 @@ impl<'de> SeqAccess<'de> for SeqAccessImpl {
 
 struct MapAccessImpl {
     iter: std::vec::IntoIter<(String, Value)>,
-    current: Option<(String, Value)>,
+    current: Option<Value>,
 }
 
 impl<'de> MapAccess<'de> for MapAccessImpl {
@@ -455,10 +574,11 @@ impl<'de> MapAccess<'de> for MapAccessImpl {
         K: DeserializeSeed<'de>,
     {
         if let Some((k, v)) = self.iter.next() {
-            self.current = Some((k.clone(), v));
-            // deserialize the key from the string
-            let key_value = StringB::from(k.clone()).to_value();
-            let de = ValueDeserializer { input: key_value };
+            // The key is moved into the key deserializer rather than cloned, and
+            // identifiers borrow it as a `&str`, so struct fields are matched without
+            // allocating per key.
+            self.current = Some(v);
+            let de = MapKeyDeserializer { key: k };
             let res = seed.deserialize(de)?;
             Ok(Some(res))
         } else {
@@ -470,16 +590,111 @@ impl<'de> MapAccess<'de> for MapAccessImpl {
     where
         V: DeserializeSeed<'de>,
     {
-        if let Some((_k, v)) = self.current.take() {
+        if let Some(v) = self.current.take() {
             let de = ValueDeserializer { input: v };
             let res = seed.deserialize(de)?;
             Ok(res)
         } else {
-            Err(SerdeValueError("value requested before key".to_string()))
+            Err(SerdeValueError::from_message("value requested before key"))
         }
     }
 }
 
+/// Deserializes an object key. Keys are always strings, so integer and boolean
+/// targets parse them back, mirroring how `MapCollector` stringifies them.
+struct MapKeyDeserializer {
+    key: String,
+}
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.key.parse::<$ty>() {
+                    Ok(parsed) => visitor.$visit(parsed),
+                    Err(_) => visitor.visit_string(self.key),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for MapKeyDeserializer {
+    type Error = SerdeValueError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.key)
+    }
+
+    deserialize_parsed_key! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+    }
+
+    /// Struct field names only need to be compared, so they are handed over borrowed.
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(&self.key)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(EnumAccessImpl {
+            name: self.key,
+            value: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple tuple_struct
+        map struct ignored_any
+    }
+}
+
 struct EnumAccessImpl {
     name: String,
     value: Option<Value>,
@@ -505,6 +720,12 @@ impl<'de> serde::de::EnumAccess<'de> for EnumAccessImpl {
     }
 }
 
+/// Builds the error for an enum variant whose payload does not match the requested shape.
+fn variant_mismatch(expected: &str, payload: Option<&Value>) -> SerdeValueError {
+    let found = payload.map_or("unit variant", |value| value.type_name());
+    SerdeValueError::invalid_type_of(expected, found, format!("expected {}", expected))
+}
+
 impl<'de> serde::de::VariantAccess<'de> for VariantAccessImpl {
     type Error = SerdeValueError;
 
@@ -512,7 +733,7 @@ impl<'de> serde::de::VariantAccess<'de> for VariantAccessImpl {
         if self.value.is_none() {
             Ok(())
         } else {
-            Err(SerdeValueError("expected unit variant".to_string()))
+            Err(variant_mismatch("unit variant", self.value.as_ref()))
         }
     }
 
@@ -523,7 +744,7 @@ impl<'de> serde::de::VariantAccess<'de> for VariantAccessImpl {
         if let Some(v) = self.value {
             seed.deserialize(ValueDeserializer { input: v })
         } else {
-            Err(SerdeValueError("expected newtype variant".to_string()))
+            Err(variant_mismatch("newtype variant", self.value.as_ref()))
         }
     }
 
@@ -537,7 +758,7 @@ impl<'de> serde::de::VariantAccess<'de> for VariantAccessImpl {
             };
             visitor.visit_seq(seq)
         } else {
-            Err(SerdeValueError("expected tuple variant".to_string()))
+            Err(variant_mismatch("tuple variant", self.value.as_ref()))
         }
     }
 
@@ -560,7 +781,7 @@ impl<'de> serde::de::VariantAccess<'de> for VariantAccessImpl {
             };
             visitor.visit_map(map)
         } else {
-            Err(SerdeValueError("expected struct variant".to_string()))
+            Err(variant_mismatch("struct variant", self.value.as_ref()))
         }
     }
 }
@@ -590,7 +811,10 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
                         } else if n.is_i128() {
                             visitor.visit_i128(n.get_i128_unsafe())
                         } else {
-                            Err(SerdeValueError("signed number out of range".to_string()))
+                            Err(SerdeValueError::new(
+                                SerdeValueErrorKind::OutOfRange,
+                                "signed number out of range",
+                            ))
                         }
                     }
                     NumberType::U8
@@ -604,17 +828,27 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
                         } else if n.is_u128() {
                             visitor.visit_u128(n.get_u128_unsafe())
                         } else {
-                            Err(SerdeValueError("unsigned number out of range".to_string()))
+                            Err(SerdeValueError::new(
+                                SerdeValueErrorKind::OutOfRange,
+                                "unsigned number out of range",
+                            ))
                         }
                     }
                     NumberType::F32 | NumberType::F64 => {
                         if let Some(f) = n.to_f64() {
                             visitor.visit_f64(f)
                         } else {
-                            Err(SerdeValueError("float number out of range".to_string()))
+                            Err(SerdeValueError::new(
+                                SerdeValueErrorKind::OutOfRange,
+                                "float number out of range",
+                            ))
                         }
                     }
-                    NumberType::Unknown => Err(SerdeValueError("unknown number type".to_string())),
+                    #[cfg(feature = "decimal")]
+                    NumberType::Decimal => visitor.visit_f64(n.to_f64().unwrap_or(0.0)),
+                    NumberType::Unknown => {
+                        Err(SerdeValueError::from_message("unknown number type"))
+                    }
                 }
             }
             Value::Array(arr) => {
@@ -635,6 +869,12 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
                 visitor.visit_map(map)
             }
             Value::Undefined => visitor.visit_unit(),
+            // Full date-times carry their offset so chrono's `Deserialize` accepts them.
+            // `Serialize` emits the same form.
+            #[cfg(feature = "chrono")]
+            Value::DateTime(DateTime::DateTime(datetime)) => {
+                visitor.visit_string(datetime.to_rfc3339())
+            }
             Value::DateTime(s) => visitor.visit_string(s.to_iso8601()),
         }
     }
@@ -734,7 +974,27 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
     where
         V: Visitor<'de>,
     {
-        self.deserialize_any(visitor)
+        match self.input {
+            Value::String(s) => {
+                let mut chars = s.as_str().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => visitor.visit_char(c),
+                    _ => Err(SerdeValueError::invalid_type_of(
+                        "char",
+                        "string",
+                        format!(
+                            "expected a single character, found a string of {} characters",
+                            s.as_str().chars().count()
+                        ),
+                    )),
+                }
+            }
+            other => Err(SerdeValueError::invalid_type_of(
+                "char",
+                other.type_name(),
+                format!("expected a char, found {}", other.type_name()),
+            )),
+        }
     }
 
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -755,14 +1015,18 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
     where
         V: Visitor<'de>,
     {
-        self.deserialize_any(visitor)
+        self.deserialize_byte_buf(visitor)
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.deserialize_any(visitor)
+        // Bytes serialized by `serialize_bytes` carry a marker; anything else is read as usual
+        match bytes_marker_payload(&self.input) {
+            Some(bytes) => visitor.visit_byte_buf(bytes),
+            None => self.deserialize_any(visitor),
+        }
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -862,6 +1126,9 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
         // Support enum representations:
         // - unit variants as string: "Variant"
         // - newtype/tuple/struct variants as single-key object: { "Variant": value }
+        // Untagged enums never reach this method: serde buffers the input through
+        // `deserialize_any` and tries each variant against it in order, so they only rely on
+        // `deserialize_any` reporting every `Value` faithfully.
         match self.input {
             Value::String(s) => {
                 // unit variant
@@ -876,12 +1143,18 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
                         value: Some(v.clone()),
                     })
                 } else {
-                    Err(SerdeValueError(
-                        "invalid enum representation: expected single-key object".to_string(),
+                    Err(SerdeValueError::invalid_type_of(
+                        "single-key object",
+                        "object",
+                        "invalid enum representation: expected single-key object",
                     ))
                 }
             }
-            _ => Err(SerdeValueError("invalid enum representation".to_string())),
+            other => Err(SerdeValueError::invalid_type_of(
+                "string or single-key object",
+                other.type_name(),
+                "invalid enum representation",
+            )),
         }
     }
 
@@ -901,13 +1174,68 @@ impl<'de> Deserializer<'de> for ValueDeserializer {
 }
 
 /// Desserializa um `Value` para qualquer `T: DeserializeOwned`.
+///
+/// Clona o `Value` inteiro; prefira `from_value_owned` quando o valor não for mais usado.
 pub fn from_value<T>(value: &Value) -> Result<T, SerdeValueError>
 where
     T: DeserializeOwned,
 {
-    T::deserialize(ValueDeserializer {
-        input: value.clone(),
-    })
+    from_value_owned(value.clone())
+}
+
+/// Desserializa um `Value` consumindo-o, sem o clone feito por `from_value`.
+pub fn from_value_owned<T>(value: Value) -> Result<T, SerdeValueError>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(ValueDeserializer { input: value })
+}
+
+/// Atualiza `target` apenas com os campos presentes em `partial`.
+///
+/// O alvo é serializado para `Value`, recebe `partial` via `deep_merge` (objetos são
+/// mesclados campo a campo, recursivamente) e é desserializado de volta. Se a conversão
+/// falhar, `target` fica intacto.
+pub fn merge_into<T>(target: &mut T, partial: &Value) -> Result<(), SerdeValueError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let mut merged = to_value(target)?;
+    merged.deep_merge(partial.clone());
+    *target = from_value_owned(merged)?;
+    Ok(())
+}
+
+impl Value {
+    /// Desserializa cada elemento de um array para `T`.
+    ///
+    /// Em caso de falha, a mensagem do erro é prefixada com o índice do primeiro elemento
+    /// inválido (`element 2: ...`), mantendo o `kind` original.
+    pub fn as_vec_of<T>(&self) -> Result<Vec<T>, SerdeValueError>
+    where
+        T: DeserializeOwned,
+    {
+        let array = match self {
+            Value::Array(array) => array,
+            other => {
+                return Err(SerdeValueError::invalid_type_of(
+                    "an array",
+                    other.type_name(),
+                    format!("invalid type: {}, expected an array", other.type_name()),
+                ))
+            }
+        };
+
+        array
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                from_value(item).map_err(|err: SerdeValueError| {
+                    SerdeValueError::new(err.kind, format!("element {}: {}", index, err.message))
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -936,6 +1264,256 @@ mod tests {
         assert_eq!(s, s2);
     }
 
+    #[test]
+    fn test_merge_into() {
+        use crate::json;
+
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Limits {
+            cpu: u32,
+            memory: u32,
+        }
+
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Service {
+            name: String,
+            replicas: u32,
+            limits: Limits,
+        }
+
+        let mut service = Service {
+            name: "api".to_string(),
+            replicas: 2,
+            limits: Limits {
+                cpu: 1,
+                memory: 512,
+            },
+        };
+        let partial = json!({"replicas": 3, "limits": {"memory": 1024}});
+
+        crate::serde_value::merge_into(&mut service, &partial).unwrap();
+        assert_eq!(
+            service,
+            Service {
+                name: "api".to_string(),
+                replicas: 3,
+                limits: Limits {
+                    cpu: 1,
+                    memory: 1024,
+                },
+            }
+        );
+
+        let invalid = json!({"replicas": "many"});
+        assert!(crate::serde_value::merge_into(&mut service, &invalid).is_err());
+        assert_eq!(service.replicas, 3);
+    }
+
+    #[test]
+    fn test_from_value_owned() {
+        let s = Simple {
+            a: 7,
+            b: "owned".to_string(),
+            c: vec![0.5],
+        };
+        let v = crate::serde_value::to_value(&s).expect("to_value failed");
+        let borrowed: Simple = crate::serde_value::from_value(&v).expect("from_value failed");
+        // Consumes `v` directly instead of cloning the tree first.
+        let owned: Simple =
+            crate::serde_value::from_value_owned(v).expect("from_value_owned failed");
+        assert_eq!(borrowed, owned);
+        assert_eq!(owned, s);
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Record {
+        id: u64,
+        name: String,
+        email: String,
+        active: bool,
+        score: f64,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn benchmark_struct_field_keys() {
+        let records = (0..2000)
+            .map(|i| Record {
+                id: i,
+                name: format!("user{}", i),
+                email: format!("user{}@example.com", i),
+                active: i % 2 == 0,
+                score: i as f64 / 4.0,
+                tags: vec!["a".to_string(), "b".to_string()],
+            })
+            .collect::<Vec<_>>();
+        let value = crate::serde_value::to_value(&records).expect("to_value failed");
+
+        // Field names are matched as borrowed `&str`s, with no per-key `String` copy.
+        let start = std::time::Instant::now();
+        let back: Vec<Record> =
+            crate::serde_value::from_value_owned(value).expect("from_value_owned failed");
+        println!(
+            "Deserialized {} records in {:?}",
+            back.len(),
+            start.elapsed()
+        );
+
+        assert_eq!(back, records);
+    }
+
+    #[test]
+    fn test_deserialize_char() {
+        let c: char = crate::serde_value::from_value(&Value::from("é")).unwrap();
+        assert_eq!(c, 'é');
+
+        let err = crate::serde_value::from_value::<char>(&Value::from("ab")).unwrap_err();
+        assert_eq!(
+            err.kind,
+            SerdeValueErrorKind::InvalidType {
+                expected: "char".to_string(),
+                found: "string".to_string()
+            }
+        );
+        assert!(crate::serde_value::from_value::<char>(&Value::from("")).is_err());
+        assert!(crate::serde_value::from_value::<char>(&Value::from(1)).is_err());
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Shape {
+        Circle,
+        Square,
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let value = Value::from(vec![("a", "not a number"), ("b", "x")]);
+        let err = crate::serde_value::from_value::<Simple>(&value).unwrap_err();
+        match &err.kind {
+            SerdeValueErrorKind::InvalidType { expected, found } => {
+                assert_eq!(expected, "i32");
+                assert_eq!(found, "string \"not a number\"");
+            }
+            other => panic!("unexpected kind: {:?}", other),
+        }
+        assert_eq!(
+            err.to_string(),
+            "SerdeValueError: invalid type: string \"not a number\", expected i32"
+        );
+
+        let value = Value::from(vec![("a", 1)]);
+        let err = crate::serde_value::from_value::<Simple>(&value).unwrap_err();
+        assert_eq!(err.kind, SerdeValueErrorKind::MissingField("b".to_string()));
+
+        let err = crate::serde_value::from_value::<u8>(&Value::from(300)).unwrap_err();
+        assert_eq!(err.kind, SerdeValueErrorKind::OutOfRange);
+
+        let err = crate::serde_value::from_value::<Shape>(&Value::from("Triangle")).unwrap_err();
+        assert_eq!(
+            err.kind,
+            SerdeValueErrorKind::UnknownVariant("Triangle".to_string())
+        );
+        assert_eq!(
+            err.message,
+            "unknown variant `Triangle`, expected `Circle` or `Square`"
+        );
+    }
+
+    #[test]
+    fn test_as_vec_of() {
+        let numbers = Value::from(vec![1, 2, 3]);
+        assert_eq!(numbers.as_vec_of::<i32>().unwrap(), vec![1, 2, 3]);
+
+        let mixed = Value::from(vec![Value::from(1), Value::from(2), Value::from("three")]);
+        let err = mixed.as_vec_of::<i32>().unwrap_err();
+        assert!(err.message.starts_with("element 2: "), "{}", err.message);
+        assert!(matches!(err.kind, SerdeValueErrorKind::InvalidType { .. }));
+
+        let err = Value::from(1).as_vec_of::<i32>().unwrap_err();
+        assert!(matches!(err.kind, SerdeValueErrorKind::InvalidType { .. }));
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(untagged)]
+    enum Untagged {
+        Count(u32),
+        Label(String),
+        Point { x: i32, y: i32 },
+        Pair(Vec<String>),
+    }
+
+    #[test]
+    fn test_untagged_enum() {
+        let count: Untagged = crate::serde_value::from_value(&Value::from(3)).unwrap();
+        assert_eq!(count, Untagged::Count(3));
+
+        let label: Untagged = crate::serde_value::from_value(&Value::from("three")).unwrap();
+        assert_eq!(label, Untagged::Label("three".to_string()));
+
+        let point: Untagged =
+            crate::serde_value::from_value(&Value::from(vec![("x", 1), ("y", 2)])).unwrap();
+        assert_eq!(point, Untagged::Point { x: 1, y: 2 });
+
+        let pair: Untagged = crate::serde_value::from_value(&Value::from(vec!["a", "b"])).unwrap();
+        assert_eq!(pair, Untagged::Pair(vec!["a".to_string(), "b".to_string()]));
+
+        let none: Result<Untagged, _> = crate::serde_value::from_value(&Value::from(true));
+        assert!(none.is_err());
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Blob {
+        #[serde(with = "serde_bytes")]
+        raw: Vec<u8>,
+        numbers: Vec<u8>,
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let blob = Blob {
+            raw: vec![1, 2, 3, 255],
+            numbers: vec![1, 2, 3, 255],
+        };
+
+        let value = crate::serde_value::to_value(&blob).expect("to_value failed");
+        assert_eq!(
+            value.get("raw"),
+            Some(&Value::from(vec![(
+                crate::serde_value::BYTES_MARKER_KEY,
+                "AQID/w=="
+            )]))
+        );
+        assert_eq!(
+            value.get("numbers"),
+            Some(&Value::from(vec![1u8, 2, 3, 255]))
+        );
+
+        let back: Blob = crate::serde_value::from_value(&value).expect("from_value failed");
+        assert_eq!(back, blob);
+    }
+
+    #[test]
+    fn test_non_string_map_keys() {
+        let mut map: HashMap<u32, String> = HashMap::new();
+        map.insert(42, "answer".to_string());
+        map.insert(7, "seven".to_string());
+
+        let value = crate::serde_value::to_value(&map).expect("to_value failed");
+        assert_eq!(value.get("42"), Some(&"answer".to_value()));
+
+        let back: HashMap<u32, String> =
+            crate::serde_value::from_value(&value).expect("from_value failed");
+        assert_eq!(back, map);
+
+        let mut flags: HashMap<bool, i32> = HashMap::new();
+        flags.insert(true, 1);
+        let value = crate::serde_value::to_value(&flags).expect("to_value failed");
+        assert_eq!(value.get("true"), Some(&1.to_value()));
+        let back: HashMap<bool, i32> =
+            crate::serde_value::from_value(&value).expect("from_value failed");
+        assert_eq!(back, flags);
+    }
+
     #[test]
     fn test_to_from_vec_and_option() {
         let v0 = vec![1i32, 2, 3];
@@ -950,6 +1528,40 @@ mod tests {
         assert_eq!(opt, opt2);
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_value_chrono() {
+        let expected = Utc.with_ymd_and_hms(2023, 4, 5, 12, 34, 56).unwrap();
+
+        let value = DateTime::from(expected).to_value();
+        let datetime: chrono::DateTime<Utc> =
+            crate::serde_value::from_value(&value).expect("from_value datetime failed");
+        assert_eq!(datetime, expected);
+
+        let value = Value::from("2023-04-05T12:34:56Z");
+        let datetime: chrono::DateTime<Utc> =
+            crate::serde_value::from_value(&value).expect("from_value string failed");
+        assert_eq!(datetime, expected);
+
+        let value = DateTime::from_ymd_opt(2023, 4, 5).to_value();
+        let date: NaiveDate =
+            crate::serde_value::from_value(&value).expect("from_value date failed");
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 4, 5).unwrap());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_datetime_string_forms_match() {
+        let value = DateTime::from(Utc.with_ymd_and_hms(2023, 4, 5, 10, 0, 0).unwrap()).to_value();
+
+        let deserialized: String =
+            crate::serde_value::from_value(&value).expect("from_value string failed");
+        let serialized = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(deserialized, "2023-04-05T10:00:00+00:00");
+        assert_eq!(serialized, format!("\"{}\"", deserialized));
+    }
+
     #[test]
     fn test_serde_number() {
         let value = Value::from(42u64);
@@ -39,11 +39,20 @@ impl Serialize for Value {
                 NumberType::I128 => serializer.serialize_i128(value.get_i128_unsafe()),
                 NumberType::F32 => serializer.serialize_f32(value.get_f32_unsafe()),
                 NumberType::F64 => serializer.serialize_f64(value.get_f64_unsafe()),
+                // JSON has no decimal type; the nearest float prints the same for typical
+                // amounts such as 0.3.
+                #[cfg(feature = "decimal")]
+                NumberType::Decimal => serializer.serialize_f64(value.to_f64().unwrap_or(0.0)),
                 NumberType::Unknown => Err(Error::custom("Unknown number type")),
             },
             Value::Boolean(value) => serializer.serialize_bool(*value),
             Value::Null => serializer.serialize_none(),
             Value::Undefined => serializer.serialize_none(),
+            // Full date-times keep their offset, the same form `from_value` presents.
+            #[cfg(feature = "chrono")]
+            Value::DateTime(DateTime::DateTime(datetime)) => {
+                serializer.serialize_str(&datetime.to_rfc3339())
+            }
             Value::DateTime(value) => serializer.serialize_str(&value.to_iso8601()),
         }
     }
@@ -28,6 +28,130 @@ mod test {
         item_f: T,
     }
 
+    #[derive(FromValue, PartialEq, Debug)]
+    #[valu3(deny_unknown_fields)]
+    struct Strict {
+        id: i32,
+        #[valu3(skip_deserializing)]
+        cache: Vec<String>,
+    }
+
+    #[derive(FromValue, PartialEq, Debug)]
+    struct Lenient {
+        id: i32,
+        #[valu3(skip_deserializing)]
+        cache: Vec<String>,
+    }
+
+    #[test]
+    fn test_deny_unknown_fields() {
+        let value = Value::from(vec![("id", 1)]);
+        assert_eq!(
+            Strict::from_value(value),
+            Some(Strict {
+                id: 1,
+                cache: vec![]
+            })
+        );
+
+        let value = Value::from(vec![("id", 1), ("extra", 2)]);
+        assert_eq!(Strict::from_value(value), None);
+    }
+
+    #[test]
+    fn test_unknown_fields_ignored_by_default() {
+        let value = Value::from(vec![("id", 1), ("extra", 2)]);
+        assert_eq!(
+            Lenient::from_value(value),
+            Some(Lenient {
+                id: 1,
+                cache: vec![]
+            })
+        );
+    }
+
+    #[test]
+    fn test_skip_deserializing() {
+        let mut map = HashMap::new();
+        map.insert("id".to_string(), Value::from(1));
+        map.insert("cache".to_string(), Value::from(vec!["stale"]));
+
+        let strict = Strict::from_value(map.to_value()).unwrap();
+        assert!(strict.cache.is_empty());
+    }
+
+    #[derive(ToValue, FromValue, PartialEq, Debug)]
+    struct Point(f64, f64);
+
+    #[test]
+    fn test_tuple_struct_roundtrip() {
+        let point = Point(1.5, -2.0);
+        let value = point.to_value();
+
+        assert_eq!(value, Value::from(vec![1.5, -2.0]));
+        assert_eq!(Point::from_value(value), Some(point));
+        assert_eq!(Point::from_value(Value::from(vec![1.5])), None);
+    }
+
+    #[derive(ToValue, FromValue, PartialEq, Debug, Clone)]
+    #[valu3(repr = "index")]
+    enum Priority {
+        Low,
+        Medium,
+        High,
+    }
+
+    #[test]
+    fn test_enum_repr_index() {
+        assert_eq!(Priority::Low.to_value(), Value::from(0u64));
+        assert_eq!(Priority::High.to_value(), Value::from(2u64));
+
+        for priority in [Priority::Low, Priority::Medium, Priority::High] {
+            assert_eq!(Priority::from_value(priority.to_value()), Some(priority));
+        }
+        assert_eq!(Priority::from_value(Value::from(1)), Some(Priority::Medium));
+        assert_eq!(Priority::from_value(Value::from(3)), None);
+        assert_eq!(Priority::from_value(Value::from("Low")), None);
+    }
+
+    mod epoch_millis {
+        use crate::prelude::*;
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        pub fn to_value(time: &SystemTime) -> Value {
+            let millis = time.duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+            Value::from(millis)
+        }
+
+        pub fn from_value(value: Value) -> Option<SystemTime> {
+            let millis = value.as_number()?.to_u64()?;
+            Some(UNIX_EPOCH + Duration::from_millis(millis))
+        }
+    }
+
+    #[derive(ToValue, FromValue, PartialEq, Debug, Clone)]
+    struct Event {
+        name: String,
+        #[valu3(with = "epoch_millis")]
+        at: std::time::SystemTime,
+    }
+
+    #[test]
+    fn test_with_module() {
+        let event = Event {
+            name: "deploy".to_string(),
+            at: std::time::UNIX_EPOCH + std::time::Duration::from_millis(1_700_000_000_123),
+        };
+        let value = event.to_value();
+
+        assert_eq!(value.get("at"), Some(&Value::from(1_700_000_000_123u64)));
+        assert_eq!(Event::from_value(value), Some(event));
+        assert_eq!(
+            Event::from_value(Value::from(vec![("name", "x"), ("at", "soon")])),
+            None
+        );
+    }
+
     #[test]
     fn test_example() {
         let example = Example {
@@ -232,6 +232,17 @@ mod benchmarks {
         
         let obj = json!(values);
         let creation_duration = start.elapsed();
+
+        // Pre-sizing the object up front avoids rehashing while the keys are inserted
+        let start = Instant::now();
+        let mut presized = Value::object_with_capacity(key_count);
+        if let Value::Object(map) = &mut presized {
+            for i in 0..key_count {
+                map.insert(format!("key_{}", i), format!("value_{}", i).to_value());
+            }
+        }
+        let presized_duration = start.elapsed();
+        assert_eq!(presized, obj);
         
         // Test access performance
         let start = Instant::now();
@@ -245,6 +256,7 @@ mod benchmarks {
         
         println!("Wide object ({} keys):", key_count);
         println!("  Creation: {:?}", creation_duration);
+        println!("  Pre-sized creation: {:?}", presized_duration);
         println!("  Access:   {:?}", access_duration);
     }
 
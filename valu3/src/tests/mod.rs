@@ -1,2 +1,4 @@
 #[cfg(feature = "derive")]
-mod derive;
\ No newline at end of file
+mod derive;
+#[cfg(feature = "derive")]
+mod value_macro;
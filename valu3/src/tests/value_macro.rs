@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_value_macro_structure() {
+        let value = value!({
+            "name": "valu3",
+            "tags": ["a", null, [1, 2]],
+            "nested": { "empty": {}, "list": [] },
+            "missing": null,
+        });
+
+        assert_eq!(value.get("name"), Some(&"valu3".to_value()));
+        assert_eq!(
+            value.get("tags"),
+            Some(&Value::from(vec![
+                "a".to_value(),
+                Value::Null,
+                Value::from(vec![1, 2])
+            ]))
+        );
+        let nested = value.get("nested").unwrap();
+        assert!(nested.get("empty").unwrap().is_object());
+        assert!(nested.get("list").unwrap().is_array());
+        assert_eq!(value.get("missing"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_value_macro_control_flow_values() {
+        let flag = false;
+        let code = 2;
+        let value = value!({
+            "level": if flag { "debug" } else { "info" },
+            "status": match code {
+                1 => "one",
+                2 => "two",
+                _ => "many",
+            },
+            "count": (1..4).count(),
+            "doubled": [1, 2].iter().map(|n| n * 2).sum::<i32>(),
+        });
+
+        assert_eq!(value.get("level"), Some(&"info".to_value()));
+        assert_eq!(value.get("status"), Some(&"two".to_value()));
+        assert_eq!(value.get("count"), Some(&3usize.to_value()));
+        assert_eq!(value.get("doubled"), Some(&6.to_value()));
+    }
+
+    #[test]
+    fn test_value_macro_expression_keys() {
+        let key = "dynamic";
+        let value = value!({ key: 1, "static": [key] });
+
+        assert_eq!(value.get("dynamic"), Some(&1.to_value()));
+        assert_eq!(value.get("static"), Some(&Value::from(vec!["dynamic"])));
+        assert_eq!(value!(null), Value::Null);
+        assert_eq!(value!(1 + 1), 2.to_value());
+    }
+
+    mod without_prelude {
+        #[test]
+        fn test_value_macro_uses_qualified_paths() {
+            let name = String::from("valu3");
+            let value = crate::prelude::value!({ "name": &name, "tags": [1, null] });
+
+            assert_eq!(
+                value.pointer("/name"),
+                Some(&crate::prelude::Value::from("valu3"))
+            );
+            assert_eq!(value.pointer("/tags/1"), Some(&crate::prelude::Value::Null));
+        }
+    }
+}
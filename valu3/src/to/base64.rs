@@ -0,0 +1,176 @@
+use crate::prelude::*;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes using the standard base64 alphabet with padding.
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        if chunk.len() > 1 {
+            out.push(ALPHABET[(n >> 6) as usize & 63] as char);
+        } else {
+            out.push('=');
+        }
+        if chunk.len() > 2 {
+            out.push(ALPHABET[n as usize & 63] as char);
+        } else {
+            out.push('=');
+        }
+    }
+    out
+}
+
+/// Decodes standard base64, with or without padding. Returns `None` on invalid input.
+pub(crate) fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=');
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in input.bytes() {
+        let sextet = ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = (buffer << 6) | sextet;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    if bits >= 6 {
+        return None;
+    }
+    Some(out)
+}
+
+impl Value {
+    /// Base64-encodes, in place, the string found at the given JSON Pointer.
+    pub fn base64_encode_field(&mut self, pointer: &str) -> Result<(), Error> {
+        let target = self
+            .pointer_mut(pointer)
+            .ok_or_else(|| Error::PointerNotFound(pointer.to_string()))?;
+        let encoded = match target {
+            Value::String(string) => base64_encode(string.as_bytes()),
+            _ => return Err(Error::NotString),
+        };
+        *target = StringB::from(encoded).to_value();
+        Ok(())
+    }
+
+    /// Decodes, in place, the base64 string found at the given JSON Pointer.
+    /// The decoded bytes must be valid UTF-8.
+    pub fn base64_decode_field(&mut self, pointer: &str) -> Result<(), Error> {
+        let target = self
+            .pointer_mut(pointer)
+            .ok_or_else(|| Error::PointerNotFound(pointer.to_string()))?;
+        let decoded = match target {
+            Value::String(string) => base64_decode(string.as_str()).ok_or(Error::InvalidBase64)?,
+            _ => return Err(Error::NotString),
+        };
+        let decoded = String::from_utf8(decoded).map_err(|_| Error::InvalidBase64)?;
+        *target = StringB::from(decoded).to_value();
+        Ok(())
+    }
+
+    /// Returns the bytes this value represents: an array of integers in `0..=255`, a
+    /// base64 string, or the `{"$bytes": "<base64>"}` object produced when serializing
+    /// bytes. Any other value, or an array holding anything else, gives `None`.
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Value::Array(array) => array
+                .into_iter()
+                .map(|item| {
+                    item.as_number()
+                        .and_then(|number| number.to_u64())
+                        .and_then(|byte| u8::try_from(byte).ok())
+                })
+                .collect(),
+            Value::String(string) => base64_decode(string.as_str()),
+            #[cfg(feature = "serde")]
+            Value::Object(_) => crate::serde_value::bytes_marker_payload(self),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode_decode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+
+        assert_eq!(base64_decode("Zg=="), Some(b"f".to_vec()));
+        assert_eq!(base64_decode("Zm8"), Some(b"fo".to_vec()));
+        assert_eq!(base64_decode("Zm9vYmFy"), Some(b"foobar".to_vec()));
+        assert_eq!(base64_decode("Z"), None);
+        assert_eq!(base64_decode("Zm9v!"), None);
+    }
+
+    #[test]
+    fn test_base64_field_roundtrip() {
+        let mut value = Value::from(vec![(
+            "file",
+            Value::from(vec![("content", "hello, wörld")]),
+        )]);
+
+        value.base64_encode_field("/file/content").unwrap();
+        assert_eq!(
+            value.pointer("/file/content"),
+            Some(&"aGVsbG8sIHfDtnJsZA==".to_value())
+        );
+
+        value.base64_decode_field("/file/content").unwrap();
+        assert_eq!(
+            value.pointer("/file/content"),
+            Some(&"hello, wörld".to_value())
+        );
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        let expected = vec![104, 105, 0, 255];
+
+        assert_eq!(
+            Value::from(vec![104, 105, 0, 255]).as_bytes(),
+            Some(expected.clone())
+        );
+        assert_eq!(Value::from("aGkA/w==").as_bytes(), Some(expected.clone()));
+        assert_eq!(
+            Value::from(vec![("$bytes", "aGkA/w==")]).as_bytes(),
+            Some(expected)
+        );
+
+        assert_eq!(Value::from(vec![1, 256]).as_bytes(), None);
+        assert_eq!(Value::from(vec![-1]).as_bytes(), None);
+        assert_eq!(Value::from("not base64!").as_bytes(), None);
+        assert_eq!(Value::from(7).as_bytes(), None);
+    }
+
+    #[test]
+    fn test_base64_field_errors() {
+        let mut value = Value::from(vec![("n", Value::from(1)), ("s", Value::from("@@"))]);
+
+        assert_eq!(
+            value.base64_encode_field("/missing"),
+            Err(Error::PointerNotFound("/missing".to_string()))
+        );
+        assert_eq!(value.base64_encode_field("/n"), Err(Error::NotString));
+        assert_eq!(value.base64_decode_field("/s"), Err(Error::InvalidBase64));
+    }
+}
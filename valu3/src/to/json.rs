@@ -1,15 +1,200 @@
 use crate::prelude::*;
 use regex::Regex;
 use serde_json::{self, Map, Value as SerdeValue};
+use std::sync::Arc;
 
 /// An enum representing the JSON output format mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum JsonMode {
     /// Outputs the JSON in an indented format.
     Indented,
     /// Outputs the JSON in an inline format.
+    #[default]
     Inline,
 }
 
+/// The line ending used between lines of indented JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Newline {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+/// A predicate deciding whether an object key is kept in the output. It is shared, so
+/// cloned options reuse it and can be sent to other threads.
+pub type KeyFilter = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Options controlling how a `Value` is rendered by `Value::to_json_with`.
+///
+/// Build it with `JsonOptions::new()` and the chained setters, or with struct update
+/// syntax over `JsonOptions::default()`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use valu3::prelude::*;
+/// let options = JsonOptions::new()
+///     .mode(JsonMode::Indented)
+///     .skip_nulls(true)
+///     .float_precision(2);
+/// let json = Value::from(vec![("pi", 3.14159)]).to_json_with(&options).unwrap();
+/// assert_eq!(json, "{\n  \"pi\": 3.14\n}");
+/// ```
+#[derive(Clone, Default)]
+pub struct JsonOptions {
+    /// The output layout.
+    pub mode: JsonMode,
+    /// Escapes every non-ASCII code point as `\uXXXX`, using surrogate pairs
+    /// for characters outside the Basic Multilingual Plane.
+    pub ascii_only: bool,
+    /// The line ending of indented output. Inline output has no line breaks.
+    pub newline: Newline,
+    /// Prefixes the output with a UTF-8 byte order mark.
+    pub bom: bool,
+    /// Cuts string values longer than this many characters, appending a
+    /// `…(truncated N chars)` marker. Meant for logs, not for round-tripping.
+    pub max_string_len: Option<usize>,
+    /// Skips object entries, at any depth, whose key the filter rejects.
+    pub key_filter: Option<KeyFilter>,
+    /// Keys written first, in this order, in every object that has them. The
+    /// remaining keys follow in alphabetical order.
+    pub key_order: Vec<String>,
+    /// Escapes `/` as `\/`, so `</script>` cannot close an HTML script block.
+    pub escape_solidus: bool,
+    /// Escapes `<` and `>` as `\u003c` and `\u003e`, for JSON embedded in HTML.
+    pub escape_script: bool,
+    /// Leaves out object entries, at any depth, whose value is `null`. Array
+    /// elements are kept so positions do not shift.
+    pub skip_nulls: bool,
+    /// Rounds floats to this many decimal places. Integers are written as is.
+    pub float_precision: Option<usize>,
+    /// Writes every C0 control character and DEL as `\uXXXX`, including those that
+    /// would otherwise use a short escape such as `\n`, so logged output cannot
+    /// drive a terminal.
+    pub escape_controls: bool,
+}
+
+impl JsonOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mode(mut self, mode: JsonMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    pub fn bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    pub fn max_string_len(mut self, max_string_len: usize) -> Self {
+        self.max_string_len = Some(max_string_len);
+        self
+    }
+
+    pub fn key_filter<F>(mut self, key_filter: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.key_filter = Some(Arc::new(key_filter));
+        self
+    }
+
+    pub fn key_order<I, S>(mut self, key_order: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.key_order = key_order.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn escape_solidus(mut self, escape_solidus: bool) -> Self {
+        self.escape_solidus = escape_solidus;
+        self
+    }
+
+    pub fn escape_script(mut self, escape_script: bool) -> Self {
+        self.escape_script = escape_script;
+        self
+    }
+
+    pub fn skip_nulls(mut self, skip_nulls: bool) -> Self {
+        self.skip_nulls = skip_nulls;
+        self
+    }
+
+    pub fn float_precision(mut self, float_precision: usize) -> Self {
+        self.float_precision = Some(float_precision);
+        self
+    }
+
+    pub fn escape_controls(mut self, escape_controls: bool) -> Self {
+        self.escape_controls = escape_controls;
+        self
+    }
+}
+
+/// Key filters compare by identity: options are equal only if they share the same filter.
+impl PartialEq for JsonOptions {
+    fn eq(&self, other: &Self) -> bool {
+        let same_filter = match (&self.key_filter, &other.key_filter) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_filter
+            && self.mode == other.mode
+            && self.ascii_only == other.ascii_only
+            && self.newline == other.newline
+            && self.bom == other.bom
+            && self.max_string_len == other.max_string_len
+            && self.key_order == other.key_order
+            && self.escape_solidus == other.escape_solidus
+            && self.escape_script == other.escape_script
+            && self.skip_nulls == other.skip_nulls
+            && self.float_precision == other.float_precision
+            && self.escape_controls == other.escape_controls
+    }
+}
+
+impl Eq for JsonOptions {}
+
+impl std::fmt::Debug for JsonOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonOptions")
+            .field("mode", &self.mode)
+            .field("ascii_only", &self.ascii_only)
+            .field("newline", &self.newline)
+            .field("bom", &self.bom)
+            .field("max_string_len", &self.max_string_len)
+            .field(
+                "key_filter",
+                &self.key_filter.as_ref().map(|_| "Fn(&str) -> bool"),
+            )
+            .field("key_order", &self.key_order)
+            .field("escape_solidus", &self.escape_solidus)
+            .field("escape_script", &self.escape_script)
+            .field("skip_nulls", &self.skip_nulls)
+            .field("float_precision", &self.float_precision)
+            .field("escape_controls", &self.escape_controls)
+            .finish()
+    }
+}
+
 impl Value {
     pub fn to_json_idented(&self) -> String {
         self.to_json(JsonMode::Indented)
@@ -20,13 +205,431 @@ impl Value {
     }
 
     pub fn to_json(&self, mode: JsonMode) -> String {
-        match self.to_serde_json_value() {
-            Ok(serde_value) => match mode {
-                JsonMode::Inline => serde_value,
-                JsonMode::Indented => Self::idented(serde_value),
+        self.to_json_ordered(mode, &[])
+            .unwrap_or_else(|e| format!("Error converting to JSON: {}", e))
+    }
+
+    /// Serializes straight into UTF-8 bytes, for writing to files and sockets. The output
+    /// matches `to_json(mode)`.
+    pub fn to_json_bytes(&self, mode: JsonMode) -> Result<Vec<u8>, SerdeValueError> {
+        let bytes = match mode {
+            JsonMode::Inline => serde_json::to_vec(self),
+            // Going through `serde_json::Value` sorts the keys, as `to_json` does.
+            JsonMode::Indented => {
+                serde_json::to_value(self).and_then(|value| serde_json::to_vec_pretty(&value))
+            }
+        };
+        bytes.map_err(|e| SerdeValueError::from_message(e.to_string()))
+    }
+
+    /// Renders the value as JSON according to `options`.
+    pub fn to_json_with(&self, options: &JsonOptions) -> Result<String, SerdeValueError> {
+        let rewrites = options.max_string_len.is_some()
+            || options.key_filter.is_some()
+            || options.skip_nulls
+            || options.float_precision.is_some();
+        let json = if rewrites {
+            let mut value = self.clone();
+            if let Some(key_filter) = &options.key_filter {
+                Self::filter_keys(&mut value, key_filter.as_ref());
+            }
+            if options.skip_nulls {
+                value.strip_nulls(false, false);
+            }
+            if let Some(max_len) = options.max_string_len {
+                Self::truncate_strings(&mut value, max_len);
+            }
+            if let Some(precision) = options.float_precision {
+                Self::round_floats(&mut value, precision);
+            }
+            value.to_json_ordered(options.mode, &options.key_order)
+        } else {
+            self.to_json_ordered(options.mode, &options.key_order)
+        };
+        let mut json = json.map_err(|e| SerdeValueError::from_message(e.to_string()))?;
+        if options.escape_controls {
+            json = Self::escape_controls(&json);
+        }
+        // `/`, `<` and `>` are not JSON syntax, so they can only occur inside strings.
+        if options.escape_solidus {
+            json = json.replace('/', "\\/");
+        }
+        if options.escape_script {
+            json = json.replace('<', "\\u003c").replace('>', "\\u003e");
+        }
+        if options.ascii_only {
+            json = Self::escape_non_ascii(&json);
+        }
+        // Line breaks inside strings are always escaped, so every raw `\n` is layout.
+        if options.newline == Newline::CrLf {
+            json = json.replace('\n', "\r\n");
+        }
+        if options.bom {
+            json.insert(0, '\u{FEFF}');
+        }
+        Ok(json)
+    }
+
+    /// Writes indented JSON with the keys of every object in sorted order, so equal values
+    /// always produce the same bytes. Meant for snapshot tests; `self` is not modified.
+    pub fn to_json_sorted(&self) -> String {
+        let mut value = self.clone();
+        Self::sort_keys(&mut value);
+        value.to_json(JsonMode::Indented)
+    }
+
+    pub fn to_json_ascii(&self, mode: JsonMode) -> String {
+        self.to_json_with(&JsonOptions::new().mode(mode).ascii_only(true))
+            .unwrap_or_else(|e| format!("Error converting to JSON: {}", e))
+    }
+
+    /// Indents arrays and objects only when they do not fit on one line.
+    ///
+    /// A container is written inline (`[1, 2]`, `{"a": 1}`) when its line, including the
+    /// indentation and key in front of it, is at most `max_width` characters; otherwise
+    /// its entries go on separate lines and the rule is applied to each of them. Object
+    /// keys are sorted, as in `JsonMode::Indented`.
+    pub fn to_json_hybrid(&self, max_width: usize) -> String {
+        let mut out = String::new();
+        Self::write_hybrid(self, 0, 0, max_width, &mut out);
+        out
+    }
+
+    fn write_hybrid(
+        value: &Value,
+        indent: usize,
+        prefix: usize,
+        max_width: usize,
+        out: &mut String,
+    ) {
+        let inline = Self::inline_spaced(value);
+        let entries: Vec<(Option<String>, &Value)> = match value {
+            Value::Array(array) => array.into_iter().map(|item| (None, item)).collect(),
+            Value::Object(object) => {
+                let mut entries = object
+                    .iter()
+                    .map(|(key, item)| (Some(key.to_string()), item))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                entries
+            }
+            _ => Vec::new(),
+        };
+        if entries.is_empty() || indent + prefix + inline.chars().count() <= max_width {
+            out.push_str(&inline);
+            return;
+        }
+
+        let (open, close) = if value.is_array() {
+            ('[', ']')
+        } else {
+            ('{', '}')
+        };
+        let pad = " ".repeat(indent + 2);
+        out.push(open);
+        for (index, (key, item)) in entries.iter().enumerate() {
+            out.push('\n');
+            out.push_str(&pad);
+            let key = key.as_ref().map_or(String::new(), |key| {
+                format!("{}: ", serde_json::to_string(key).unwrap_or_default())
+            });
+            out.push_str(&key);
+            Self::write_hybrid(item, indent + 2, key.chars().count(), max_width, out);
+            if index + 1 < entries.len() {
+                out.push(',');
+            }
+        }
+        out.push('\n');
+        out.push_str(&" ".repeat(indent));
+        out.push(close);
+    }
+
+    /// Single-line JSON with a space after each `,` and `:`, and keys sorted.
+    fn inline_spaced(value: &Value) -> String {
+        match value {
+            Value::Array(array) => format!(
+                "[{}]",
+                array
+                    .into_iter()
+                    .map(Self::inline_spaced)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Value::Object(object) => {
+                let mut entries = object
+                    .iter()
+                    .map(|(key, item)| (key.to_string(), item))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                format!(
+                    "{{{}}}",
+                    entries
+                        .into_iter()
+                        .map(|(key, item)| format!(
+                            "{}: {}",
+                            serde_json::to_string(&key).unwrap_or_default(),
+                            Self::inline_spaced(item)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+            _ => value.to_json(JsonMode::Inline),
+        }
+    }
+
+    fn to_json_ordered(
+        &self,
+        mode: JsonMode,
+        key_order: &[String],
+    ) -> Result<String, serde_json::Error> {
+        if key_order.is_empty() {
+            let json = self.to_serde_json_value()?;
+            return Ok(match mode {
+                JsonMode::Inline => json,
+                JsonMode::Indented => Self::idented(json),
+            });
+        }
+        // Indenting through `serde_json::Value` would sort the keys again, so the
+        // ordered view is serialized directly in both modes.
+        let ordered = OrderedKeys {
+            value: self,
+            key_order,
+        };
+        match mode {
+            JsonMode::Inline => serde_json::to_string(&ordered),
+            JsonMode::Indented => serde_json::to_string_pretty(&ordered),
+        }
+    }
+
+    fn filter_keys(value: &mut Value, key_filter: &dyn Fn(&str) -> bool) {
+        match value {
+            Value::Array(array) => array
+                .values
+                .iter_mut()
+                .for_each(|item| Self::filter_keys(item, key_filter)),
+            Value::Object(object) => match object {
+                Object::BTreeMap(map) => {
+                    map.retain(|key, _| key_filter(&key.to_string()));
+                    map.values_mut()
+                        .for_each(|item| Self::filter_keys(item, key_filter));
+                }
+                Object::HashMap(map) => {
+                    map.retain(|key, _| key_filter(&key.to_string()));
+                    map.values_mut()
+                        .for_each(|item| Self::filter_keys(item, key_filter));
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn truncate_strings(value: &mut Value, max_len: usize) {
+        match value {
+            Value::String(string) => {
+                let total = string.as_str().chars().count();
+                if total > max_len {
+                    let kept = string.as_str().chars().take(max_len).collect::<String>();
+                    *value = Value::from(format!("{}…(truncated {} chars)", kept, total - max_len));
+                }
+            }
+            Value::Array(array) => array
+                .values
+                .iter_mut()
+                .for_each(|item| Self::truncate_strings(item, max_len)),
+            Value::Object(object) => match object {
+                Object::BTreeMap(map) => map
+                    .values_mut()
+                    .for_each(|item| Self::truncate_strings(item, max_len)),
+                Object::HashMap(map) => map
+                    .values_mut()
+                    .for_each(|item| Self::truncate_strings(item, max_len)),
+            },
+            _ => {}
+        }
+    }
+
+    fn sort_keys(value: &mut Value) {
+        match value {
+            Value::Array(array) => array.values.iter_mut().for_each(Self::sort_keys),
+            Value::Object(object) => {
+                if let Object::HashMap(map) = object {
+                    *object = Object::BTreeMap(std::mem::take(map).into_iter().collect());
+                }
+                if let Object::BTreeMap(map) = object {
+                    map.values_mut().for_each(Self::sort_keys);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn round_floats(value: &mut Value, precision: usize) {
+        match value {
+            Value::Number(number) if number.is_float() => {
+                // A scale or scaled value that overflows would round to infinity and be
+                // written as null, so such numbers are left as they are.
+                let scale = i32::try_from(precision).map(|exp| 10f64.powi(exp));
+                if let (Some(float), Ok(scale)) = (number.to_f64(), scale) {
+                    let scaled = float * scale;
+                    if scale.is_finite() && scaled.is_finite() {
+                        *value = Value::from(scaled.round() / scale);
+                    }
+                }
+            }
+            Value::Array(array) => array
+                .values
+                .iter_mut()
+                .for_each(|item| Self::round_floats(item, precision)),
+            Value::Object(object) => match object {
+                Object::BTreeMap(map) => map
+                    .values_mut()
+                    .for_each(|item| Self::round_floats(item, precision)),
+                Object::HashMap(map) => map
+                    .values_mut()
+                    .for_each(|item| Self::round_floats(item, precision)),
             },
-            Err(e) => format!("Error converting to JSON: {}", e),
+            _ => {}
+        }
+    }
+
+    /// serde_json already escapes C0 characters, some in short form; those are rewritten
+    /// as `\uXXXX` and DEL, which it leaves raw, is escaped too.
+    fn escape_controls(json: &str) -> String {
+        let mut escaped = String::with_capacity(json.len());
+        let mut chars = json.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('b') => escaped.push_str("\\u0008"),
+                    Some('t') => escaped.push_str("\\u0009"),
+                    Some('n') => escaped.push_str("\\u000a"),
+                    Some('f') => escaped.push_str("\\u000c"),
+                    Some('r') => escaped.push_str("\\u000d"),
+                    Some(next) => {
+                        escaped.push('\\');
+                        escaped.push(next);
+                    }
+                    None => escaped.push('\\'),
+                },
+                '\u{7f}' => escaped.push_str("\\u007f"),
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    /// Non-ASCII characters can only appear inside JSON strings, so the
+    /// serialized output can be escaped as a whole.
+    fn escape_non_ascii(json: &str) -> String {
+        let mut escaped = String::with_capacity(json.len());
+        let mut units = [0u16; 2];
+        for c in json.chars() {
+            if c.is_ascii() {
+                escaped.push(c);
+            } else {
+                for unit in c.encode_utf16(&mut units) {
+                    escaped.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+        }
+        escaped
+    }
+
+    /// Writes an array of objects as JSON Lines: one inline object per line.
+    ///
+    /// The keys of the first row act as the header schema; every other row must be an
+    /// object with exactly the same keys.
+    #[cfg(feature = "serde")]
+    pub fn to_jsonl(&self) -> Result<String, SerdeValueError> {
+        let rows = match self {
+            Value::Array(array) => array,
+            _ => {
+                return Err(SerdeValueError::from_message(
+                    "JSON Lines output requires an array of objects",
+                ))
+            }
+        };
+
+        let mut header: Option<Vec<String>> = None;
+        let mut output = String::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            let object = match row {
+                Value::Object(object) => object,
+                _ => {
+                    return Err(SerdeValueError::from_message(format!(
+                        "JSON Lines row {} is not an object",
+                        index
+                    )))
+                }
+            };
+
+            let mut keys = object
+                .keys()
+                .iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>();
+            keys.sort();
+            match &header {
+                Some(header) if *header != keys => {
+                    return Err(SerdeValueError::from_message(format!(
+                        "JSON Lines row {} does not match the header keys {:?}",
+                        index, header
+                    )))
+                }
+                Some(_) => {}
+                None => header = Some(keys),
+            }
+
+            let line = serde_json::to_string(row)
+                .map_err(|e| SerdeValueError::from_message(e.to_string()))?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    /// Serializes the value to JSON and percent-encodes it for safe inclusion in a URL.
+    /// Only RFC 3986 unreserved characters are left as-is.
+    pub fn to_json_urlencoded(&self, mode: JsonMode) -> String {
+        let json = self.to_json(mode);
+        let mut encoded = String::with_capacity(json.len() * 3);
+        for byte in json.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    encoded.push(byte as char)
+                }
+                _ => encoded.push_str(&format!("%{:02X}", byte)),
+            }
         }
+        encoded
+    }
+
+    /// Decodes a percent-encoded JSON string produced by `to_json_urlencoded` and parses it.
+    #[cfg(feature = "parser")]
+    pub fn json_from_urlencoded(encoded: &str) -> Result<Value, Error> {
+        let bytes = encoded.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut index = 0;
+        while index < bytes.len() {
+            if bytes[index] == b'%' {
+                let byte = encoded
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| {
+                        Error::NonParsebleMsg(format!("Invalid percent-encoding at {}", index))
+                    })?;
+                decoded.push(byte);
+                index += 3;
+            } else {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+
+        let json = String::from_utf8(decoded)
+            .map_err(|e| Error::NonParsebleMsg(format!("Invalid UTF-8: {}", e)))?;
+        Value::json_to_value(&json)
     }
 
     /// Converte o Value interno em serde_json::Value usando apenas APIs de serde.
@@ -44,6 +647,57 @@ impl Value {
     }
 }
 
+/// Serializes a value with the keys of every object rearranged by `key_order`.
+struct OrderedKeys<'a> {
+    value: &'a Value,
+    key_order: &'a [String],
+}
+
+impl serde::Serialize for OrderedKeys<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{SerializeMap, SerializeSeq};
+
+        let nested = |value| OrderedKeys {
+            value,
+            key_order: self.key_order,
+        };
+        match self.value {
+            Value::Object(object) => {
+                let mut entries = object
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(a, _), (b, _)| {
+                    let rank = |key: &String| {
+                        self.key_order
+                            .iter()
+                            .position(|ordered| ordered == key)
+                            .unwrap_or(self.key_order.len())
+                    };
+                    rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
+                });
+
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(&key, &nested(value))?;
+                }
+                map.end()
+            }
+            Value::Array(array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+                for item in array {
+                    seq.serialize_element(&nested(item))?;
+                }
+                seq.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +722,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_should_write_json_lines() {
+        let rows = Value::from(vec![
+            Value::from(vec![("id", 1)]),
+            Value::from(vec![("id", 2)]),
+            Value::from(vec![("id", 3)]),
+        ]);
+        assert_eq!(
+            rows.to_jsonl().unwrap(),
+            "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n"
+        );
+
+        let rows = Value::from(vec![Value::from(vec![("id", 1)]), Value::from(2)]);
+        assert!(rows.to_jsonl().is_err());
+
+        let rows = Value::from(vec![
+            Value::from(vec![("id", 1)]),
+            Value::from(vec![("name", 2)]),
+        ]);
+        assert!(rows.to_jsonl().is_err());
+        assert!(Value::from(1).to_jsonl().is_err());
+    }
+
+    #[test]
+    fn it_should_escape_non_ascii_characters() {
+        let value = Value::from(vec![("name", "café")]);
+        assert_eq!(
+            value.to_json_ascii(JsonMode::Inline),
+            r#"{"name":"caf\u00e9"}"#
+        );
+
+        let value = Value::from("😀");
+        assert_eq!(
+            value
+                .to_json_with(&JsonOptions {
+                    mode: JsonMode::Inline,
+                    ascii_only: true,
+                    ..JsonOptions::default()
+                })
+                .unwrap(),
+            r#""\ud83d\ude00""#
+        );
+        assert_eq!(
+            value.to_json_with(&JsonOptions::default()).unwrap(),
+            "\"😀\""
+        );
+    }
+
+    #[test]
+    fn it_should_apply_newline_and_bom_options() {
+        let value = Value::from(vec![("text", "a\nb")]);
+        let options = JsonOptions {
+            mode: JsonMode::Indented,
+            newline: Newline::CrLf,
+            ..JsonOptions::default()
+        };
+        assert_eq!(
+            value.to_json_with(&options).unwrap(),
+            "{\r\n  \"text\": \"a\\nb\"\r\n}"
+        );
+
+        let options = JsonOptions {
+            bom: true,
+            ..JsonOptions::default()
+        };
+        let json = value.to_json_with(&options).unwrap();
+        assert!(json.starts_with('\u{FEFF}'));
+        assert_eq!(&json['\u{FEFF}'.len_utf8()..], "{\"text\":\"a\\nb\"}");
+        assert_eq!(json.as_bytes()[..3], [0xEF, 0xBB, 0xBF]);
+    }
+
+    #[test]
+    fn it_should_truncate_long_strings() {
+        let html = format!("<html>{}</html>", "x".repeat(1000));
+        let value = Value::from(vec![
+            ("body", Value::from(html.as_str())),
+            ("tags", Value::from(vec!["short", "éééééé"])),
+        ]);
+        let options = JsonOptions {
+            max_string_len: Some(5),
+            ..JsonOptions::default()
+        };
+
+        let json = Value::json_to_value(&value.to_json_with(&options).unwrap()).unwrap();
+        assert_eq!(
+            json.get("body"),
+            Some(&"<html…(truncated 1008 chars)".to_value())
+        );
+        assert_eq!(
+            json.get("tags"),
+            Some(&Value::from(vec!["short", "ééééé…(truncated 1 chars)"]))
+        );
+        assert_eq!(value.get("body"), Some(&html.to_value()));
+    }
+
+    #[test]
+    fn it_should_skip_filtered_keys() {
+        let value = Value::from(vec![
+            ("id", Value::from(1)),
+            ("_token", Value::from("secret")),
+            (
+                "items",
+                Value::from(vec![Value::from(vec![("name", "a"), ("_internal", "x")])]),
+            ),
+        ]);
+        let options = JsonOptions {
+            key_filter: Some(Arc::new(|key: &str| !key.starts_with('_'))),
+            ..JsonOptions::default()
+        };
+
+        let json = value.to_json_with(&options).unwrap();
+        assert!(!json.contains("_token"));
+        assert!(!json.contains("_internal"));
+        assert_eq!(
+            Value::json_to_value(&json).unwrap(),
+            Value::from(vec![
+                ("id", Value::from(1)),
+                ("items", Value::from(vec![Value::from(vec![("name", "a")])])),
+            ])
+        );
+        assert!(value.get("_token").is_some());
+    }
+
+    #[test]
+    fn it_should_share_options_with_a_key_filter() {
+        let options = JsonOptions::new().key_filter(|key| key != "secret");
+        let shared = options.clone();
+        assert_eq!(shared, options);
+        assert_ne!(shared, JsonOptions::new().key_filter(|key| key != "secret"));
+
+        let value = Value::from(vec![("id", 1), ("secret", 2)]);
+        let json = std::thread::spawn(move || value.to_json_with(&shared).unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(json, r#"{"id":1}"#);
+    }
+
+    #[test]
+    fn it_should_roundtrip_urlencoded_json() {
+        let value = Value::json_to_value(r#"{"q":"a b&c=d/é","n":[1,2]}"#).unwrap();
+        let encoded = value.to_json_urlencoded(JsonMode::Inline);
+
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.~%".contains(c)));
+        assert_eq!(Value::json_from_urlencoded(&encoded), Ok(value));
+    }
+
+    #[test]
+    fn it_should_reject_invalid_urlencoding() {
+        assert!(Value::json_from_urlencoded("%7B%ZZ").is_err());
+        assert!(Value::json_from_urlencoded("%7").is_err());
+    }
+
     #[test]
     fn it_should_complex_string() {
         let string = r#"1 1763496849266 https://mercado.carrefour.com.br/mapa-do-site/1 <!DOCTYPE html><html lang="pt-BR"><head><link href="https://cdn-prod.securiti.ai/consent/cookie-consent-latest.css" rel="stylesheet"><meta charset="utf-8"><meta name="viewport" content="width=device-width, initial-scale=1.0, maximum-scale=1.0,user-scalable=0"><title>Mapa do Site | Supermercado Carrefour</title><meta name="robots" content="index,follow"><meta name="description" content="O Carrefour também tem supermercado online! Faça sua lista de compras e aproveite nosso delivery. Aproveite!"><meta property="og:title" content="Mercado Carrefour: Ofertas de Supermercado Delivery"><meta property="og:description" content="O Carrefour também tem supermercado online! Faça sua lista de compras e aproveite nosso delivery. Aproveite!"><meta property="og:url" content="https://mercado.carrefour.com.br"><meta property="og:type" content="website"><link rel="canonical" href="https://mercado.carrefour.com.br"><meta name="next-head-count" content="10"><meta name="google-site-verification" content="GjAwJWf5U8gd7i0Tg-Dqz8LE0qi4RWdMWxfwsd-EgOY"><meta name="facebook-domain-verification" content="ym08vcfms00jx3fkqdkgqgsxrxbi8f"><meta name="facebook-domain-verification" content="ym08vcfms00jx3fkqdkgqgsxrxbi8f"><link rel="preconnect" href="https://fonts.googleapis.com"><link rel="preload" href="https://fonts.googleapis.com"><link href="https://fonts.googleapis.com/css2?family=Ubuntu:ital,wght@0,300;0,400;0,500;0,700;1,300;1,400;1,500;1,700&amp;display=swap" rel="preload"><link href="https://fonts.googleapis.com/css2?family=Lato:ital,wght@0,700;1,300&amp;display=swap" rel="preload"><link rel="preload" href="/_next/static/css/4a6cfdceadc6be2d.css" as="style"><link rel="stylesheet" href="/_next/static/css/4a6cfdceadc6be2d.css" data-n-g=""><link rel="preload" href="/_next/static/css/d2bb7ebb3aa1fe96.css" as="style"><link rel="stylesheet" href="/_next/static/css/d2bb7ebb3aa1fe96.css" data-n-p=""><noscript data-n-css=""></noscript><script defer="" nomodule="" src="/_next/static/chunks/polyfills-c67a75d1b6f99dc8.js"></script><script data-partytown-config="">"#;
@@ -78,4 +886,188 @@ mod tests {
 
         assert_eq!(json_output, expected);
     }
+
+    #[test]
+    fn it_should_emit_keys_in_the_given_order() {
+        let value = Value::from(vec![
+            ("updated_at", Value::from("2024-01-02")),
+            ("name", Value::from("widget")),
+            ("id", Value::from(7)),
+            ("color", Value::from("red")),
+            (
+                "parts",
+                Value::from(vec![Value::from(vec![("name", "bolt"), ("id", "p1")])]),
+            ),
+        ]);
+        let options = || JsonOptions {
+            key_order: vec!["id".to_string(), "name".to_string()],
+            ..JsonOptions::default()
+        };
+
+        assert_eq!(
+            value.to_json_with(&options()).unwrap(),
+            r#"{"id":7,"name":"widget","color":"red","parts":[{"id":"p1","name":"bolt"}],"updated_at":"2024-01-02"}"#
+        );
+
+        let indented = value
+            .to_json_with(&JsonOptions {
+                mode: JsonMode::Indented,
+                ..options()
+            })
+            .unwrap();
+        assert!(indented.starts_with("{\n  \"id\": 7,\n  \"name\": \"widget\",\n  \"color\""));
+        assert_eq!(Value::json_to_value(&indented), Ok(value));
+    }
+
+    #[test]
+    fn it_should_inline_only_what_fits() {
+        let value = Value::from(vec![
+            ("id", Value::from(1)),
+            ("tags", Value::from(vec!["a", "b"])),
+            (
+                "matrix",
+                Value::from(vec![
+                    Value::from(vec![100000, 200000, 300000]),
+                    Value::from(vec![400000, 500000, 600000]),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(
+            value.to_json_hybrid(200),
+            r#"{"id": 1, "matrix": [[100000, 200000, 300000], [400000, 500000, 600000]], "tags": ["a", "b"]}"#
+        );
+        assert_eq!(
+            value.to_json_hybrid(40),
+            "{\n  \"id\": 1,\n  \"matrix\": [\n    [100000, 200000, 300000],\n    [400000, 500000, 600000]\n  ],\n  \"tags\": [\"a\", \"b\"]\n}"
+        );
+        assert_eq!(Value::json_to_value(&value.to_json_hybrid(0)), Ok(value));
+        assert_eq!(Value::from(Vec::<Value>::new()).to_json_hybrid(0), "[]");
+    }
+
+    #[test]
+    fn it_should_escape_for_script_blocks() {
+        let value = Value::from(vec![("html", "<b>x</b></script>")]);
+
+        let json = value
+            .to_json_with(&JsonOptions {
+                escape_solidus: true,
+                ..JsonOptions::default()
+            })
+            .unwrap();
+        assert_eq!(json, r#"{"html":"<b>x<\/b><\/script>"}"#);
+        let decoded: SerdeValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded["html"], "<b>x</b></script>");
+
+        let json = value
+            .to_json_with(&JsonOptions {
+                escape_solidus: true,
+                escape_script: true,
+                ..JsonOptions::default()
+            })
+            .unwrap();
+        assert_eq!(
+            json,
+            r#"{"html":"\u003cb\u003ex\u003c\/b\u003e\u003c\/script\u003e"}"#
+        );
+        assert!(!json.contains("</script"));
+    }
+
+    #[test]
+    fn it_should_combine_builder_options() {
+        let value = Value::from(vec![
+            ("name", Value::from("café")),
+            ("nickname", Value::Null),
+            ("ratio", Value::from(0.123456)),
+            ("tags", Value::from(vec![Value::Null, Value::from("ü")])),
+        ]);
+        let options = JsonOptions::new()
+            .mode(JsonMode::Indented)
+            .skip_nulls(true)
+            .ascii_only(true)
+            .float_precision(3);
+
+        assert_eq!(
+            value.to_json_with(&options).unwrap(),
+            "{\n  \"name\": \"caf\\u00e9\",\n  \"ratio\": 0.123,\n  \"tags\": [\n    null,\n    \"\\u00fc\"\n  ]\n}"
+        );
+        assert_eq!(value.get("nickname"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn it_should_keep_floats_that_would_overflow_when_rounded() {
+        let value = Value::from(vec![Value::from(1e300), Value::from(1.5)]);
+
+        assert_eq!(
+            value
+                .to_json_with(&JsonOptions::new().float_precision(10))
+                .unwrap(),
+            "[1e300,1.5]"
+        );
+        assert_eq!(
+            value
+                .to_json_with(&JsonOptions::new().float_precision(400))
+                .unwrap(),
+            "[1e300,1.5]"
+        );
+    }
+
+    #[test]
+    fn it_should_write_sorted_snapshots() {
+        let value = Value::from(vec![
+            ("zeta", Value::from(1)),
+            (
+                "alpha",
+                Value::from(vec![Value::from(vec![("b", 2), ("a", 1)])]),
+            ),
+            ("mid", Value::Null),
+        ]);
+        let expected = "{\n  \"alpha\": [\n    {\n      \"a\": 1,\n      \"b\": 2\n    }\n  ],\n  \"mid\": null,\n  \"zeta\": 1\n}";
+
+        assert_eq!(value.to_json_sorted(), expected);
+        for _ in 0..10 {
+            let shuffled = Value::from(vec![
+                ("mid", Value::Null),
+                (
+                    "alpha",
+                    Value::from(vec![Value::from(vec![("a", 1), ("b", 2)])]),
+                ),
+                ("zeta", Value::from(1)),
+            ]);
+            assert_eq!(shuffled.to_json_sorted(), expected);
+        }
+    }
+
+    #[test]
+    fn it_should_write_json_bytes() {
+        let value = Value::from(vec![
+            ("name", Value::from("café")),
+            ("ratio", Value::from(0.25)),
+            ("tags", Value::from(vec![Value::from(1), Value::Null])),
+        ]);
+
+        for mode in [JsonMode::Inline, JsonMode::Indented] {
+            assert_eq!(
+                value.to_json_bytes(mode).unwrap(),
+                value.to_json(mode).into_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_escape_control_characters() {
+        let value = Value::from(vec![("log", "ding\u{7}\tend\u{7f}\\n")]);
+        let options = JsonOptions::new().escape_controls(true);
+
+        let json = value.to_json_with(&options).unwrap();
+        assert_eq!(json, r#"{"log":"ding\u0007\u0009end\u007f\\n"}"#);
+        assert!(!json.chars().any(|c| c.is_control()));
+        let decoded: SerdeValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded["log"], "ding\u{7}\tend\u{7f}\\n");
+
+        assert_eq!(
+            value.to_json(JsonMode::Inline),
+            "{\"log\":\"ding\\u0007\\tend\u{7f}\\\\n\"}"
+        );
+    }
 }
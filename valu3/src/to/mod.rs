@@ -1,3 +1,7 @@
+pub mod base64;
 pub mod json;
+#[cfg(feature = "serde")]
+pub mod multipart;
+pub mod pretty;
 pub mod yaml;
 // pub mod bin;
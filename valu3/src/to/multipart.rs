@@ -0,0 +1,212 @@
+use crate::prelude::*;
+use crate::to::base64::base64_decode;
+
+impl Value {
+    /// Renders a flat object as a `multipart/form-data` body delimited by `boundary`.
+    ///
+    /// Strings, numbers, booleans and dates become text fields. An object with a
+    /// `filename` and a `content` entry (and optionally a `content_type`) becomes a file
+    /// part; its content is either a string or a `{"$bytes": "<base64>"}` marker. Fields
+    /// are written in key order so the output is deterministic. The caller must pick a
+    /// boundary that does not occur in any of the values.
+    ///
+    /// The boundary must be 1 to 70 characters from the set RFC 2046 allows, and must
+    /// not end with a space. A `content_type` containing CR or LF is rejected, since it
+    /// is written into the part header as is.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use valu3::prelude::*;
+    /// let form = Value::from(vec![("name", "ana")]);
+    /// let body = form.to_multipart("XyZ").unwrap();
+    /// assert!(body.starts_with(b"--XyZ\r\n"));
+    /// ```
+    pub fn to_multipart(&self, boundary: &str) -> Result<Vec<u8>, SerdeValueError> {
+        let object = match self {
+            Value::Object(object) => object,
+            _ => {
+                return Err(SerdeValueError::from_message(
+                    "multipart output requires an object",
+                ))
+            }
+        };
+        if !is_valid_boundary(boundary) {
+            return Err(SerdeValueError::from_message(
+                "multipart boundary must be 1 to 70 RFC 2046 characters",
+            ));
+        }
+
+        let mut fields = object.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(key, _)| key.to_string());
+
+        let mut body = Vec::new();
+        for (key, value) in fields {
+            let name = multipart_quote(&key.to_string());
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            match value {
+                Value::String(_) | Value::Number(_) | Value::Boolean(_) | Value::DateTime(_) => {
+                    body.extend_from_slice(
+                        format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", name)
+                            .as_bytes(),
+                    );
+                    body.extend_from_slice(value.to_string().as_bytes());
+                }
+                Value::Object(_) => {
+                    let (filename, content_type, content) = multipart_file(&name, value)?;
+                    body.extend_from_slice(
+                        format!(
+                            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                            name,
+                            multipart_quote(&filename)
+                        )
+                        .as_bytes(),
+                    );
+                    body.extend_from_slice(
+                        format!("Content-Type: {}\r\n\r\n", content_type).as_bytes(),
+                    );
+                    body.extend_from_slice(&content);
+                }
+                _ => {
+                    return Err(SerdeValueError::from_message(format!(
+                        "multipart field \"{}\" must be a scalar or a file object",
+                        name
+                    )))
+                }
+            }
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+        Ok(body)
+    }
+}
+
+/// Extracts the filename, content type and raw content of a file part.
+fn multipart_file(name: &str, value: &Value) -> Result<(String, String, Vec<u8>), SerdeValueError> {
+    let invalid = || {
+        SerdeValueError::from_message(format!(
+            "multipart file \"{}\" needs a string filename and a string or bytes content",
+            name
+        ))
+    };
+
+    let filename = match value.get("filename") {
+        Some(Value::String(filename)) => filename.to_string(),
+        _ => return Err(invalid()),
+    };
+    let content_type = match value.get("content_type") {
+        Some(Value::String(content_type)) => {
+            let content_type = content_type.to_string();
+            if content_type.contains(['\r', '\n']) {
+                return Err(SerdeValueError::from_message(format!(
+                    "multipart file \"{}\" has a content_type with a line break",
+                    name
+                )));
+            }
+            content_type
+        }
+        None => "application/octet-stream".to_string(),
+        _ => return Err(invalid()),
+    };
+    let content = match value.get("content") {
+        Some(Value::String(content)) => content.as_str().as_bytes().to_vec(),
+        Some(Value::Object(marker)) if marker.len() == 1 => match marker.get(BYTES_MARKER_KEY) {
+            Some(Value::String(encoded)) => base64_decode(encoded.as_str()).ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        },
+        _ => return Err(invalid()),
+    };
+    Ok((filename, content_type, content))
+}
+
+/// Checks a boundary against RFC 2046: 1 to 70 `bchars`, the last one not a space.
+fn is_valid_boundary(boundary: &str) -> bool {
+    (1..=70).contains(&boundary.len())
+        && !boundary.ends_with(' ')
+        && boundary
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "'()+_,-./:=? ".contains(c))
+}
+
+/// Percent-encodes the characters that would end a quoted header parameter.
+fn multipart_quote(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_text_fields() {
+        let form = Value::from(vec![("name", Value::from("ana")), ("age", Value::from(30))]);
+        let body = String::from_utf8(form.to_multipart("BOUNDARY").unwrap()).unwrap();
+
+        assert_eq!(
+            body,
+            "--BOUNDARY\r\n\
+             Content-Disposition: form-data; name=\"age\"\r\n\r\n\
+             30\r\n\
+             --BOUNDARY\r\n\
+             Content-Disposition: form-data; name=\"name\"\r\n\r\n\
+             ana\r\n\
+             --BOUNDARY--\r\n"
+        );
+    }
+
+    #[test]
+    fn renders_file_parts() {
+        let form = Value::from(vec![(
+            "upload",
+            Value::from(vec![
+                ("filename", Value::from("a.bin")),
+                ("content", Value::from(vec![(BYTES_MARKER_KEY, "AAEC")])),
+            ]),
+        )]);
+        let body = form.to_multipart("b").unwrap();
+
+        let mut expected = b"--b\r\n\
+            Content-Disposition: form-data; name=\"upload\"; filename=\"a.bin\"\r\n\
+            Content-Type: application/octet-stream\r\n\r\n"
+            .to_vec();
+        expected.extend_from_slice(&[0, 1, 2]);
+        expected.extend_from_slice(b"\r\n--b--\r\n");
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn rejects_unsupported_shapes() {
+        assert!(Value::from(vec![1]).to_multipart("b").is_err());
+        assert!(Value::from(vec![("list", vec![1, 2])])
+            .to_multipart("b")
+            .is_err());
+        assert!(Value::from(vec![("name", "x")]).to_multipart("").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_boundaries() {
+        let form = Value::from(vec![("name", "x")]);
+        assert!(form.to_multipart("a\r\nX-Injected: 1").is_err());
+        assert!(form.to_multipart(&"b".repeat(71)).is_err());
+        assert!(form.to_multipart("ends with space ").is_err());
+        assert!(form.to_multipart("semi;colon").is_err());
+        assert!(form.to_multipart(&"b".repeat(70)).is_ok());
+        assert!(form.to_multipart("----=_Part 0.1'(x)+,/:?").is_ok());
+    }
+
+    #[test]
+    fn rejects_content_type_line_breaks() {
+        let form = Value::from(vec![(
+            "upload",
+            Value::from(vec![
+                ("filename", "a.txt"),
+                ("content", "hi"),
+                ("content_type", "text/plain\r\nX-Injected: 1"),
+            ]),
+        )]);
+        assert!(form.to_multipart("b").is_err());
+    }
+}
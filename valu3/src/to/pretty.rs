@@ -0,0 +1,135 @@
+use crate::prelude::*;
+
+/// Longest string `to_pretty_string` prints before cutting it short.
+pub const DEFAULT_PRETTY_STRING_LEN: usize = 80;
+
+impl Value {
+    /// Formats the value for people rather than parsers.
+    ///
+    /// Integers get thousands separators, floats drop trailing zeros, dates are written
+    /// in ISO 8601 and strings longer than `DEFAULT_PRETTY_STRING_LEN` characters are
+    /// truncated with an ellipsis. The output is not JSON; use `to_json` for interchange.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use valu3::prelude::*;
+    /// assert_eq!(Value::from(1000000).to_pretty_string(), "1,000,000");
+    /// ```
+    pub fn to_pretty_string(&self) -> String {
+        self.to_pretty_string_with(DEFAULT_PRETTY_STRING_LEN)
+    }
+
+    /// Like `to_pretty_string`, truncating strings after `max_string_len` characters.
+    pub fn to_pretty_string_with(&self, max_string_len: usize) -> String {
+        match self {
+            Value::String(string) => truncate_pretty(string.as_str(), max_string_len),
+            _ => pretty_nested(self, max_string_len),
+        }
+    }
+}
+
+/// Formats a value inside a container, where strings are quoted to stay readable.
+fn pretty_nested(value: &Value, max_string_len: usize) -> String {
+    match value {
+        Value::String(string) => {
+            format!("\"{}\"", truncate_pretty(string.as_str(), max_string_len))
+        }
+        Value::Number(number) => pretty_number(number),
+        Value::Boolean(boolean) => boolean.to_string(),
+        Value::DateTime(datetime) => datetime.to_iso8601(),
+        Value::Null => "null".to_string(),
+        Value::Undefined => "undefined".to_string(),
+        Value::Array(array) => format!(
+            "[{}]",
+            array
+                .into_iter()
+                .map(|item| pretty_nested(item, max_string_len))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(object) => format!(
+            "{{{}}}",
+            object
+                .iter()
+                .map(|(key, item)| format!("{}: {}", key, pretty_nested(item, max_string_len)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn pretty_number(number: &Number) -> String {
+    // `Display` already prints floats in their shortest form, without trailing zeros.
+    let plain = number.to_string();
+    let (sign, unsigned) = match plain.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", plain.as_str()),
+    };
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(dot) => unsigned.split_at(dot),
+        None => (unsigned, ""),
+    };
+    if !integer.bytes().all(|byte| byte.is_ascii_digit()) {
+        return plain;
+    }
+
+    let mut grouped = String::with_capacity(integer.len() + integer.len() / 3);
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{}{}{}", sign, grouped, fraction)
+}
+
+fn truncate_pretty(string: &str, max_len: usize) -> String {
+    match string.char_indices().nth(max_len) {
+        Some((end, _)) => format!("{}…", &string[..end]),
+        None => string.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers() {
+        assert_eq!(Value::from(1000000).to_pretty_string(), "1,000,000");
+        assert_eq!(Value::from(-1234567i64).to_pretty_string(), "-1,234,567");
+        assert_eq!(Value::from(999).to_pretty_string(), "999");
+        assert_eq!(Value::from(2.50).to_pretty_string(), "2.5");
+        assert_eq!(Value::from(4.0).to_pretty_string(), "4");
+        assert_eq!(Value::from(12345.75).to_pretty_string(), "12,345.75");
+    }
+
+    #[test]
+    fn truncates_long_strings() {
+        let long = "a".repeat(200);
+        let pretty = Value::from(long.as_str()).to_pretty_string();
+        assert_eq!(pretty.chars().count(), DEFAULT_PRETTY_STRING_LEN + 1);
+        assert!(pretty.ends_with('…'));
+
+        assert_eq!(
+            Value::from("héllo wörld").to_pretty_string_with(5),
+            "héllo…"
+        );
+        assert_eq!(Value::from("short").to_pretty_string_with(5), "short");
+    }
+
+    #[test]
+    fn formats_containers_and_dates() {
+        let value = Value::from(vec![
+            Value::from(1500),
+            Value::from("x"),
+            Value::from(DateTime::from("2024-03-01")),
+            Value::Null,
+        ]);
+        assert_eq!(value.to_pretty_string(), "[1,500, \"x\", 2024-03-01, null]");
+
+        let value = Value::from(vec![("total", 25000)]);
+        assert_eq!(value.to_pretty_string(), "{total: 25,000}");
+    }
+}
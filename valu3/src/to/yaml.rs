@@ -1,114 +1,275 @@
-use crate::value::Value;
+use crate::prelude::*;
 
 impl Value {
-    /// Returns the YAML representation of the given `Value` with the specified indentation.
+    /// Renders the value as block-style YAML.
     ///
-    /// # Arguments
-    ///
-    /// * `indent` - The number of spaces to use for indentation.
+    /// Objects become `key: value` lines and arrays become `-` items, indented by two
+    /// spaces per level. Strings that YAML would otherwise read as another type
+    /// (numbers, booleans, `null`, ...) are double-quoted.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use json_utils::{Value, StringB};
-    /// let value = Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]);
-    /// assert_eq!(value.to_yaml_with_indent(2), " - 1\n   - 2\n   - 3\n".to_string());
+    /// # use valu3::prelude::*;
+    /// let value = Value::from(vec![Value::from(1), Value::from(2)]);
+    /// assert_eq!(value.to_yaml().unwrap(), "- 1\n- 2\n".to_string());
     /// ```
+    ///
+    /// # Errors
+    ///
+    /// Every `Value` can be rendered, so this currently always returns `Ok`. The `Result`
+    /// matches the other serde-backed writers such as `to_json_with`, leaving room to
+    /// report unrepresentable input later without changing the signature.
+    #[cfg(feature = "serde")]
+    pub fn to_yaml(&self) -> Result<String, SerdeValueError> {
+        Ok(render_yaml(self, 0))
+    }
+
+    /// Renders the value as block-style YAML with every line indented by `indent` spaces.
+    #[deprecated(since = "0.9.6", note = "use `Value::to_yaml` instead")]
     pub fn to_yaml_with_indent(&self, indent: usize) -> String {
-        let prefix = " ".repeat(indent);
-        match self {
-            Value::Null => " null\n".to_string(),
-            Value::Boolean(b) => format!(" {}\n", b),
-            Value::Number(n) => format!(" {}\n", n),
-            Value::String(s) => format!(r#" "{}"\n"#, s.to_string()),
-            Value::Array(a) => {
-                let elements: Vec<String> = a
-                    .into_iter()
-                    .map(|v| format!(" - {}", v.to_yaml_with_indent(indent + 2)))
-                    .collect();
-                format!("\n{}", elements.join(""))
+        render_yaml(self, indent)
+    }
+}
+
+fn render_yaml(value: &Value, indent: usize) -> String {
+    let mut output = String::new();
+    match yaml_scalar(value) {
+        Some(scalar) => {
+            output.push_str(&" ".repeat(indent));
+            output.push_str(&scalar);
+            output.push('\n');
+        }
+        None => write_yaml_block(value, indent, &mut output),
+    }
+    output
+}
+
+/// Writes a non-empty array or object, every line prefixed by `indent` spaces.
+fn write_yaml_block(value: &Value, indent: usize, output: &mut String) {
+    let prefix = " ".repeat(indent);
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter() {
+                output.push_str(&prefix);
+                output.push_str(&yaml_string(&key.to_string()));
+                output.push(':');
+                match yaml_scalar(value) {
+                    Some(scalar) => {
+                        output.push(' ');
+                        output.push_str(&scalar);
+                        output.push('\n');
+                    }
+                    None => {
+                        output.push('\n');
+                        write_yaml_block(value, indent + 2, output);
+                    }
+                }
             }
-            Value::Object(o) => {
-                let elements: Vec<String> = o
-                    .iter()
-                    .map(|(k, v)| format!("{}{}:{}", prefix, k, v.to_yaml_with_indent(indent + 2)))
-                    .collect();
-                if indent > 0 {
-                    format!("\n{}", elements.join(""))
-                } else {
-                    elements.join("")
+        }
+        Value::Array(array) => {
+            for item in array.into_iter() {
+                match yaml_scalar(item) {
+                    Some(scalar) => {
+                        output.push_str(&prefix);
+                        output.push_str("- ");
+                        output.push_str(&scalar);
+                        output.push('\n');
+                    }
+                    None => {
+                        // The first line of a nested block shares the `- ` marker line.
+                        let mut nested = String::new();
+                        write_yaml_block(item, indent + 2, &mut nested);
+                        output.push_str(&prefix);
+                        output.push_str("- ");
+                        output.push_str(&nested[indent + 2..]);
+                    }
                 }
             }
-            Value::Undefined => " ~\n".to_string(),
-            Value::DateTime(dt) => format!(" {}\n", dt.to_string()),
         }
+        _ => {}
     }
+}
 
-    /// Returns the YAML representation of the given `Value`.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// # use json_utils::{Value, StringB};
-    /// let value = Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]);
-    /// assert_eq!(value.to_yaml(), "- 1\n  - 2\n  - 3\n".to_string());
-    /// ```
-    pub fn to_yaml(&self) -> String {
-        self.to_yaml_with_indent(0).to_string()
+/// Returns the inline form of scalars and empty collections, or `None` for block content.
+fn yaml_scalar(value: &Value) -> Option<String> {
+    let scalar = match value {
+        Value::Null | Value::Undefined => "null".to_string(),
+        Value::Boolean(value) => value.to_string(),
+        Value::Number(number) => match number.get_f64() {
+            Some(float) if number.is_float() && float.is_nan() => ".nan".to_string(),
+            Some(float) if number.is_float() && float.is_infinite() => {
+                if float > 0.0 {
+                    ".inf".to_string()
+                } else {
+                    "-.inf".to_string()
+                }
+            }
+            _ => number.to_string(),
+        },
+        Value::String(string) => yaml_string(string.as_str()),
+        Value::DateTime(datetime) => datetime.to_string(),
+        Value::Array(array) if array.is_empty() => "[]".to_string(),
+        Value::Object(object) if object.is_empty() => "{}".to_string(),
+        Value::Array(_) | Value::Object(_) => return None,
+    };
+    Some(scalar)
+}
+
+/// Emits a string plain when that is unambiguous, double-quoted otherwise.
+fn yaml_string(string: &str) -> String {
+    if yaml_needs_quotes(string) {
+        let mut quoted = String::with_capacity(string.len() + 2);
+        quoted.push('"');
+        for c in string.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    } else {
+        string.to_string()
     }
 }
 
-#[test]
-fn test_to_yaml() {
-    use std::collections::BTreeMap;
+fn yaml_needs_quotes(string: &str) -> bool {
+    const RESERVED: &[&str] = &[
+        "null", "~", "true", "false", "yes", "no", "on", "off", "y", "n", ".nan", ".inf", "-.inf",
+        "+.inf",
+    ];
+
+    let first = match string.chars().next() {
+        Some(first) => first,
+        None => return true,
+    };
+
+    RESERVED.contains(&string.to_lowercase().as_str())
+        || string.parse::<f64>().is_ok()
+        || Number::parse_lenient(string).is_ok()
+        || string.trim() != string
+        || "-?:,[]{}#&*!|>'\"%@`".contains(first)
+        || string.ends_with(':')
+        || string.contains(": ")
+        || string.contains(" #")
+        || string.chars().any(|c| c.is_control())
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
     use crate::prelude::*;
 
-    let object = Object::from(
-        vec![
-            ("null_value".to_string(), Value::Null),
-            ("boolean_value".to_string(), Value::Boolean(true)),
-            ("number_value".to_string(), Number::from(42).to_value()),
-            (
-                "string_value".to_string(),
-                StringB::from("Hello, world!".to_string()).to_value(),
-            ),
-            (
-                "array_value".to_string(),
-                Array::from(vec![
-                    Number::from(1).to_value(),
-                    Number::from(2).to_value(),
-                    Number::from(3).to_value(),
-                ])
-                .to_value(),
-            ),
+    fn ordered(entries: Vec<(&str, Value)>) -> Value {
+        Object::from(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<std::collections::BTreeMap<String, Value>>(),
+        )
+        .to_value()
+    }
+
+    #[test]
+    fn test_to_yaml_block_style() {
+        let value = ordered(vec![
+            ("name", Value::from("valu3")),
+            ("version", Value::from("1.0")),
+            ("enabled", Value::from(true)),
+            ("nothing", Value::Null),
+            ("tags", Value::from(vec!["json", "yes"])),
             (
-                "object_value".to_string(),
-                Object::from(
-                    vec![
-                        (
-                            "key1".to_string(),
-                            StringB::from("value1".to_string()).to_value(),
-                        ),
-                        (
-                            "key2".to_string(),
-                            StringB::from("value2".to_string()).to_value(),
-                        ),
-                    ]
-                    .into_iter()
-                    .collect::<BTreeMap<String, Value>>(),
-                )
-                .to_value(),
+                "owners",
+                Value::from(vec![ordered(vec![
+                    ("id", Value::from(1)),
+                    ("level", Value::from(2)),
+                ])]),
             ),
-            ("undefined_value".to_string(), Value::Undefined),
-        ]
-        .into_iter()
-        .collect::<BTreeMap<String, Value>>(),
-    );
-
-    let value = Value::Object(object);
-    let yaml_output = value.to_yaml();
-    let mut yaml_lines: Vec<_> = yaml_output.lines().collect();
-    yaml_lines.sort();
-
-    assert!(true);
+            ("matrix", Value::from(vec![vec![1, 2], vec![3]])),
+            ("empty", Value::from(Vec::<Value>::new())),
+        ]);
+
+        assert_eq!(
+            value.to_yaml().unwrap(),
+            "empty: []\n\
+             enabled: true\n\
+             matrix:\n  - - 1\n    - 2\n  - - 3\n\
+             name: valu3\n\
+             nothing: null\n\
+             owners:\n  - id: 1\n    level: 2\n\
+             tags:\n  - json\n  - \"yes\"\n\
+             version: \"1.0\"\n"
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_to_yaml_with_indent() {
+        let value = ordered(vec![
+            ("a", Value::from(vec![1, 2])),
+            ("b", Value::from("x")),
+        ]);
+
+        assert_eq!(value.to_yaml_with_indent(0), value.to_yaml().unwrap());
+        assert_eq!(
+            value.to_yaml_with_indent(4),
+            "    a:\n      - 1\n      - 2\n    b: x\n"
+        );
+        assert_eq!(Value::from(1).to_yaml_with_indent(2), "  1\n");
+    }
+
+    #[test]
+    fn test_to_yaml_quotes_ambiguous_strings() {
+        for (input, expected) in [
+            ("true", "\"true\"\n"),
+            ("null", "\"null\"\n"),
+            ("42", "\"42\"\n"),
+            ("", "\"\"\n"),
+            ("a: b", "\"a: b\"\n"),
+            ("line\nbreak", "\"line\\nbreak\"\n"),
+            ("plain text", "plain text\n"),
+        ] {
+            assert_eq!(Value::from(input).to_yaml().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_to_yaml_datetime() {
+        let value = DateTime::from("2023-04-05T10:00:00Z").to_value();
+        assert_eq!(value.to_yaml().unwrap(), "2023-04-05T10:00:00+00:00\n");
+    }
+
+    #[test]
+    fn test_to_yaml_round_trip() {
+        let value = Value::json_to_value(
+            r#"{
+                "name": "valu3",
+                "version": "1.0",
+                "greeting": "hello, world",
+                "quoted": ["yes", "null", "", "a: b", "line\nbreak", "-1"],
+                "owners": [{"id": 1, "tags": ["x", "y"]}, {"id": 2, "tags": []}],
+                "matrix": [[1, 2], [[3], 4]],
+                "nested": {"deep": {"ratio": 0.5, "off": false, "none": null, "map": {}}}
+            }"#,
+        )
+        .unwrap();
+
+        let yaml = value.to_yaml().unwrap();
+        assert_eq!(Value::yaml_to_value(&yaml), Ok(value));
+
+        // YAML has no date-time type here: the ISO 8601 scalar reads back as a string
+        // naming the same instant.
+        let datetime = DateTime::from("2023-04-05T10:00:00Z");
+        let value = Value::from(vec![("at", datetime.to_value())]);
+        let parsed = Value::yaml_to_value(&value.to_yaml().unwrap()).unwrap();
+        assert_eq!(
+            parsed.get("at").map(|at| DateTime::from(at.as_str())),
+            Some(datetime)
+        );
+    }
 }
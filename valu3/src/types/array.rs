@@ -77,6 +77,24 @@ impl Array {
         self.values.push(value);
     }
 
+    /// Inserts a value at the front of the array.
+    ///
+    /// The array is backed by a `Vec`, so this shifts every element and costs O(n).
+    /// For long queues, collect into a `VecDeque` and convert back when done.
+    pub fn push_front(&mut self, value: Value) {
+        self.values.insert(0, value);
+    }
+
+    /// Removes and returns the first value, or `None` if the array is empty. Costs O(n),
+    /// like `push_front`.
+    pub fn pop_front(&mut self) -> Option<Value> {
+        if self.values.is_empty() {
+            None
+        } else {
+            Some(self.values.remove(0))
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.values.len()
     }
@@ -84,8 +102,63 @@ impl Array {
     pub fn is_empty(&self) -> bool {
         self.values.is_empty()
     }
-}
 
+    /// Replaces the values in `range` with `replacement` and returns the removed values.
+    /// The range is clamped to the array bounds, so an out-of-range start appends.
+    pub fn splice(&mut self, range: std::ops::Range<usize>, replacement: Vec<Value>) -> Vec<Value> {
+        let start = range.start.min(self.values.len());
+        let end = range.end.clamp(start, self.values.len());
+        self.values.splice(start..end, replacement).collect()
+    }
+
+    /// Removes duplicate values, keeping the first occurrence of each one in its original position.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use my_crate::{Array, Value};
+    ///
+    /// let mut array = Array::from(vec![1, 2, 2, 1, 3]);
+    /// array.dedup();
+    ///
+    /// assert_eq!(array, Array::from(vec![1, 2, 3]));
+    /// ```
+    pub fn dedup(&mut self) {
+        let mut unique: Vec<Value> = Vec::with_capacity(self.values.len());
+        for value in self.values.drain(..) {
+            if !unique.contains(&value) {
+                unique.push(value);
+            }
+        }
+        self.values = unique;
+    }
+
+    /// Removes objects whose value for `key` was already seen, keeping the first occurrence.
+    /// Elements that are not objects or do not contain `key` are always kept.
+    pub fn dedup_by_key<T>(&mut self, key: T)
+    where
+        T: ValueKeyBehavior,
+    {
+        let mut seen: Vec<Value> = Vec::new();
+        let mut unique: Vec<Value> = Vec::with_capacity(self.values.len());
+        for value in self.values.drain(..) {
+            let field = match &value {
+                Value::Object(object) => object.get(key.clone()).cloned(),
+                _ => None,
+            };
+
+            match field {
+                Some(field) if seen.contains(&field) => {}
+                Some(field) => {
+                    seen.push(field);
+                    unique.push(value);
+                }
+                None => unique.push(value),
+            }
+        }
+        self.values = unique;
+    }
+}
 
 impl ArrayBehavior for Array {
     fn pop(&mut self) -> Option<Value> {
@@ -187,6 +260,56 @@ impl<K: AsRef<str>, V: Into<Value>> From<BTreeMap<K, V>> for Array {
     }
 }
 
+/// Converts each element of an array value with `convert`, reporting the first element
+/// that is not a number or does not fit.
+fn numeric_elements<T>(
+    value: &Value,
+    expected: &'static str,
+    convert: fn(&Number) -> Option<T>,
+) -> Result<Vec<T>, Error> {
+    let array = value.expect_array()?;
+    array
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            item.as_number()
+                .and_then(convert)
+                .ok_or(Error::InvalidElement {
+                    index,
+                    expected,
+                    found: item.type_name(),
+                })
+        })
+        .collect()
+}
+
+impl TryFrom<&Value> for Vec<i64> {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        numeric_elements(value, "i64", |number| number.to_i64())
+    }
+}
+
+impl TryFrom<&Value> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        numeric_elements(value, "u8", |number| {
+            number.to_u64().and_then(|n| u8::try_from(n).ok())
+        })
+    }
+}
+
+/// Integers are widened to `f64`, possibly losing precision above 2^53.
+impl TryFrom<&Value> for Vec<f64> {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        numeric_elements(value, "f64", |number| number.to_f64())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -205,6 +328,17 @@ mod tests {
         assert_eq!(array.pop(), Some(Value::from(42)));
     }
 
+    #[test]
+    fn array_push_pop_front() {
+        let mut array = Array::from(vec![2, 3]);
+        array.push_front(Value::from(1));
+        assert_eq!(array, Array::from(vec![1, 2, 3]));
+        assert_eq!(array.pop_front(), Some(Value::from(1)));
+        assert_eq!(array.pop_front(), Some(Value::from(2)));
+        assert_eq!(array.pop_front(), Some(Value::from(3)));
+        assert_eq!(array.pop_front(), None);
+    }
+
     #[test]
     fn array_len() {
         let mut array = Array::new();
@@ -229,6 +363,52 @@ mod tests {
         assert_eq!(array.get(0), Some(&Value::from(84)));
     }
 
+    #[test]
+    fn array_splice() {
+        let mut array = Array::from(vec![0, 1, 2, 3, 4]);
+        let removed = array.splice(1..3, vec![Value::from(10), Value::from(20)]);
+
+        assert_eq!(removed, vec![Value::from(1), Value::from(2)]);
+        assert_eq!(array, Array::from(vec![0, 10, 20, 3, 4]));
+
+        let removed = array.splice(4..10, vec![]);
+        assert_eq!(removed, vec![Value::from(4)]);
+
+        let removed = array.splice(10..12, vec![Value::from(5)]);
+        assert!(removed.is_empty());
+        assert_eq!(array, Array::from(vec![0, 10, 20, 3, 5]));
+    }
+
+    #[test]
+    fn array_dedup() {
+        let mut array = Array::from(vec![1, 2, 2, 1, 3]);
+        array.dedup();
+        assert_eq!(array, Array::from(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn array_dedup_by_key() {
+        let object = |id: i32, name: &str| {
+            let mut map = HashMap::new();
+            map.insert("id", Value::from(id));
+            map.insert("name", Value::from(name));
+            Value::from(map)
+        };
+
+        let mut array = Array::from(vec![
+            object(1, "a"),
+            object(2, "b"),
+            object(1, "c"),
+            Value::from(42),
+        ]);
+        array.dedup_by_key("id");
+
+        assert_eq!(
+            array,
+            Array::from(vec![object(1, "a"), object(2, "b"), Value::from(42)])
+        );
+    }
+
     #[test]
     fn array_from_value() {
         let array = Array::from(Value::from(42));
@@ -297,4 +477,35 @@ mod tests {
 
         assert!(found_key1 && found_key2);
     }
+
+    #[test]
+    fn numeric_vec_try_from() {
+        let value = Value::from(vec![Value::from(1), Value::from(2.5), Value::from(-3i64)]);
+        assert_eq!(Vec::<f64>::try_from(&value), Ok(vec![1.0, 2.5, -3.0]));
+
+        let value = Value::from(vec![Value::from(1), Value::from("2"), Value::from(3)]);
+        assert_eq!(
+            Vec::<f64>::try_from(&value),
+            Err(Error::InvalidElement {
+                index: 1,
+                expected: "f64",
+                found: "string"
+            })
+        );
+
+        assert_eq!(
+            Vec::<i64>::try_from(&Value::from(vec![7, 8])),
+            Ok(vec![7, 8])
+        );
+        assert!(Vec::<i64>::try_from(&Value::from(vec![1.5])).is_err());
+        assert_eq!(
+            Vec::<u8>::try_from(&Value::from(vec![0, 255])),
+            Ok(vec![0, 255])
+        );
+        assert!(Vec::<u8>::try_from(&Value::from(vec![256])).is_err());
+        assert!(matches!(
+            Vec::<i64>::try_from(&Value::from(1)),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
 }
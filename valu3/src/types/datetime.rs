@@ -49,13 +49,56 @@ pub trait DateTimeBehavior {
 /// * `Date(NaiveDate)` - Represents a date without timezone information.
 /// * `Time(NaiveTime)` - Represents a time without date and timezone information.
 /// * `DateTime(ChDateTime<chrono::Utc>)` - Represents a date-time with timezone information.
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+///
+/// Values are ordered chronologically. A `Date` stands for midnight UTC, so it can be
+/// compared with a `DateTime`; a `Time` only compares with another `Time`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum DateTime {
     Date(NaiveDate),
     Time(NaiveTime),
     DateTime(ChDateTime<chrono::Utc>),
 }
 
+impl DateTime {
+    /// Returns how much later `self` is than `other`, negative if it is earlier.
+    ///
+    /// Dates count as midnight UTC. Returns `None` when a time of day is compared with
+    /// a date or a date-time, since they have no common timeline.
+    pub fn diff(&self, other: &DateTime) -> Option<Duration> {
+        match (self, other) {
+            (DateTime::Time(a), DateTime::Time(b)) => Some(*a - *b),
+            _ => Some(self.instant()? - other.instant()?),
+        }
+    }
+
+    fn instant(&self) -> Option<ChDateTime<Utc>> {
+        match self {
+            DateTime::Date(date) => Some(date.and_hms_opt(0, 0, 0)?.and_utc()),
+            DateTime::Time(_) => None,
+            DateTime::DateTime(datetime) => Some(*datetime),
+        }
+    }
+
+    fn variant_rank(&self) -> u8 {
+        match self {
+            DateTime::Date(_) => 0,
+            DateTime::Time(_) => 1,
+            DateTime::DateTime(_) => 2,
+        }
+    }
+}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        let ordering = match (self, other) {
+            (DateTime::Time(a), DateTime::Time(b)) => a.cmp(b),
+            _ => self.instant()?.cmp(&other.instant()?),
+        };
+        // A date and a date-time at midnight are distinct values; keep them unequal.
+        Some(ordering.then(self.variant_rank().cmp(&other.variant_rank())))
+    }
+}
+
 // Implementations of From trait to allow conversion from NaiveDate, NaiveTime, and ChDateTime<Utc>
 impl From<NaiveDate> for DateTime {
     fn from(value: NaiveDate) -> Self {
@@ -304,7 +347,7 @@ impl DateTimeBehavior for DateTime {
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
-    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
 
     #[test]
     fn test_add_duration() {
@@ -361,6 +404,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_chronological_order() {
+        let morning = DateTime::with_ymd_and_hms(2023, 4, 5, 9, 0, 0);
+        let evening = DateTime::with_ymd_and_hms(2023, 4, 5, 18, 30, 0);
+        let next_day = DateTime::from_ymd_opt(2023, 4, 6);
+
+        assert!(morning < evening);
+        assert!(next_day > evening);
+        assert!(DateTime::from_ymd_opt(2023, 4, 5) < morning);
+        assert_eq!(evening.diff(&morning), Some(Duration::minutes(9 * 60 + 30)));
+        assert_eq!(morning.diff(&next_day), Some(Duration::hours(-15)));
+
+        let noon = DateTime::from(NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+        assert_eq!(noon.partial_cmp(&morning), None);
+        assert_eq!(noon.diff(&morning), None);
+        assert_eq!(
+            morning.add_duration(Duration::hours(1)),
+            Some(DateTime::with_ymd_and_hms(2023, 4, 5, 10, 0, 0))
+        );
+    }
+
     #[test]
     fn test_timestamp() {
         let date = NaiveDate::from_ymd_opt(2023, 4, 5).unwrap();
@@ -0,0 +1,72 @@
+use crate::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+
+/// A `Value` that can only be read.
+///
+/// `FrozenValue` hands out nothing but shared references, so code holding one can
+/// `get`, `pointer`, iterate and serialize the tree but has no way to change it.
+/// `into_value` gives the value back when an owner does need to edit it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrozenValue(Value);
+
+impl FrozenValue {
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+}
+
+impl Deref for FrozenValue {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        &self.0
+    }
+}
+
+impl Display for FrozenValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl From<Value> for FrozenValue {
+    fn from(value: Value) -> Self {
+        FrozenValue(value)
+    }
+}
+
+impl Value {
+    /// Wraps the value in a read-only `FrozenValue`.
+    pub fn freeze(self) -> FrozenValue {
+        FrozenValue(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_freeze_read_access() {
+        let config = Value::from(vec![
+            ("name", Value::from("service")),
+            ("ports", Value::from(vec![80, 443])),
+        ])
+        .freeze();
+
+        assert_eq!(config.get("name"), Some(&"service".to_value()));
+        assert_eq!(config.pointer("/ports/1"), Some(&443.to_value()));
+        let ports = match config.get("ports") {
+            Some(Value::Array(ports)) => ports.into_iter().collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        assert_eq!(ports.len(), 2);
+
+        let shared = config.clone();
+        let mut value = shared.into_value();
+        value.insert("name", "worker");
+        assert_eq!(config.get("name"), Some(&"service".to_value()));
+        assert_eq!(value.get("name"), Some(&"worker".to_value()));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::prelude::*;
+use chrono::{NaiveDate, NaiveTime};
+
+/// The variants a `Value` can take, without their contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    String,
+    Number,
+    Boolean,
+    Array,
+    Object,
+    Null,
+    Undefined,
+    DateTime,
+}
+
+impl ValueKind {
+    /// Returns the lowercase name of the kind, e.g. `"object"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValueKind::String => "string",
+            ValueKind::Number => "number",
+            ValueKind::Boolean => "boolean",
+            ValueKind::Array => "array",
+            ValueKind::Object => "object",
+            ValueKind::Null => "null",
+            ValueKind::Undefined => "undefined",
+            ValueKind::DateTime => "datetime",
+        }
+    }
+}
+
+impl Value {
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::String(_) => ValueKind::String,
+            Value::Number(_) => ValueKind::Number,
+            Value::Boolean(_) => ValueKind::Boolean,
+            Value::Array(_) => ValueKind::Array,
+            Value::Object(_) => ValueKind::Object,
+            Value::Null => ValueKind::Null,
+            Value::Undefined => ValueKind::Undefined,
+            Value::DateTime(_) => ValueKind::DateTime,
+        }
+    }
+
+    /// Converts the value to `target` on a best-effort basis.
+    ///
+    /// Scalars convert between each other where there is an obvious meaning: `"true"` and
+    /// `"false"` (any case) become booleans, numeric strings become numbers, booleans become
+    /// `1`/`0`, numbers become `true` unless zero, and every scalar can be rendered as a string.
+    /// Strings in ISO 8601 form become date-times. Casting to the value's own kind returns a
+    /// copy; anything else fails with `Error::InvalidCast`.
+    pub fn cast(&self, target: ValueKind) -> Result<Value, Error> {
+        if self.kind() == target {
+            return Ok(self.clone());
+        }
+
+        let invalid = || Error::InvalidCast {
+            from: self.type_name(),
+            to: target.name(),
+        };
+
+        match (self, target) {
+            (Value::Number(_) | Value::Boolean(_) | Value::DateTime(_), ValueKind::String) => {
+                Ok(StringB::from(self.to_string()).to_value())
+            }
+            (Value::String(string), ValueKind::Boolean) => {
+                match string.as_str().trim().to_lowercase().as_str() {
+                    "true" => Ok(Value::Boolean(true)),
+                    "false" => Ok(Value::Boolean(false)),
+                    _ => Err(invalid()),
+                }
+            }
+            (Value::Number(number), ValueKind::Boolean) => match number.to_f64() {
+                Some(float) if !float.is_nan() => Ok(Value::Boolean(float != 0.0)),
+                _ => Err(invalid()),
+            },
+            (Value::String(string), ValueKind::Number) => Number::try_from(string.as_str().trim())
+                .map(Value::Number)
+                .map_err(|_| invalid()),
+            (Value::Boolean(boolean), ValueKind::Number) => {
+                Ok(Number::from(if *boolean { 1 } else { 0 }).to_value())
+            }
+            (Value::String(string), ValueKind::DateTime) => {
+                let string = string.as_str().trim();
+                if let Ok(date) = string.parse::<NaiveDate>() {
+                    Ok(DateTime::Date(date).to_value())
+                } else if let Ok(time) = string.parse::<NaiveTime>() {
+                    Ok(DateTime::Time(time).to_value())
+                } else if let Ok(datetime) = string.parse::<chrono::DateTime<chrono::Utc>>() {
+                    Ok(DateTime::DateTime(datetime).to_value())
+                } else {
+                    Err(invalid())
+                }
+            }
+            (Value::Undefined, ValueKind::Null) => Ok(Value::Null),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cast_supported() {
+        assert_eq!(
+            Value::from("TRUE").cast(ValueKind::Boolean),
+            Ok(Value::from(true))
+        );
+        assert_eq!(
+            Value::from(" 42 ").cast(ValueKind::Number),
+            Ok(Value::from(42))
+        );
+        assert_eq!(
+            Value::from(1.5).cast(ValueKind::String),
+            Ok(Value::from("1.5"))
+        );
+        assert_eq!(
+            Value::from(true).cast(ValueKind::String),
+            Ok(Value::from("true"))
+        );
+        assert_eq!(
+            Value::from(true).cast(ValueKind::Number),
+            Ok(Value::from(1))
+        );
+        assert_eq!(
+            Value::from(0).cast(ValueKind::Boolean),
+            Ok(Value::from(false))
+        );
+        assert_eq!(
+            Value::from("2023-04-05").cast(ValueKind::DateTime),
+            Ok(DateTime::from("2023-04-05").to_value())
+        );
+        assert_eq!(Value::Undefined.cast(ValueKind::Null), Ok(Value::Null));
+
+        let list = Value::from(vec![1, 2]);
+        assert_eq!(list.cast(ValueKind::Array), Ok(list.clone()));
+    }
+
+    #[test]
+    fn test_cast_impossible() {
+        let object = Value::from(vec![("a", 1)]);
+        let error = object.cast(ValueKind::Number).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::InvalidCast {
+                from: "object",
+                to: "number"
+            }
+        );
+        assert_eq!(error.to_string(), "cannot cast object to number");
+        assert!(Value::from("yes please").cast(ValueKind::Boolean).is_err());
+        assert!(Value::from("abc").cast(ValueKind::Number).is_err());
+    }
+}
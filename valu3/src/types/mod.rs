@@ -3,4 +3,8 @@ pub mod array;
 pub mod object;
 pub mod number;
 pub mod datetime;
-pub mod stringb;
\ No newline at end of file
+pub mod kind;
+pub mod stringb;
+pub mod frozen;
+#[cfg(feature = "shared")]
+pub mod shared;
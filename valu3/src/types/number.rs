@@ -6,6 +6,8 @@
 //!
 //! The `NumberType` enum is used to identify the type of number stored in a `Number` instance.
 use crate::prelude::*;
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
 use std::fmt::Display;
 
 pub trait NumberBehavior {
@@ -185,6 +187,9 @@ pub enum NumberType {
     I128,
     F32,
     F64,
+    /// A fixed-point decimal, available with the `decimal` feature.
+    #[cfg(feature = "decimal")]
+    Decimal,
     Unknown,
 }
 
@@ -211,6 +216,51 @@ pub struct Number {
     pub i128: Option<i128>,
     pub f32: Option<f32>,
     pub f64: Option<f64>,
+    #[cfg(feature = "decimal")]
+    pub decimal: Option<Decimal>,
+}
+
+/// An arithmetic operation, applied by `Number::combine_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl ArithmeticOp {
+    /// The exact integer result, or `None` on overflow or (for `Div`) an inexact quotient.
+    fn integer(self, a: i128, b: i128) -> Option<i128> {
+        match self {
+            ArithmeticOp::Add => a.checked_add(b),
+            ArithmeticOp::Sub => a.checked_sub(b),
+            ArithmeticOp::Mul => a.checked_mul(b),
+            ArithmeticOp::Div => match a.checked_rem(b) {
+                Some(0) => a.checked_div(b),
+                _ => None,
+            },
+        }
+    }
+
+    fn float(self, a: f64, b: f64) -> f64 {
+        match self {
+            ArithmeticOp::Add => a + b,
+            ArithmeticOp::Sub => a - b,
+            ArithmeticOp::Mul => a * b,
+            ArithmeticOp::Div => a / b,
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    fn decimal(self, a: Decimal, b: Decimal) -> Option<Decimal> {
+        match self {
+            ArithmeticOp::Add => a.checked_add(b),
+            ArithmeticOp::Sub => a.checked_sub(b),
+            ArithmeticOp::Mul => a.checked_mul(b),
+            ArithmeticOp::Div => a.checked_div(b),
+        }
+    }
 }
 
 impl Number {
@@ -241,8 +291,245 @@ impl Number {
         self.i128 = None;
         self.f32 = None;
         self.f64 = None;
+        #[cfg(feature = "decimal")]
+        {
+            self.decimal = None;
+        }
         self
     }
+
+    /// Wraps a fixed-point decimal. Arithmetic between decimals, or a decimal and an
+    /// integer, stays exact: `0.1 + 0.2` is `0.3`, not `0.30000000000000004`.
+    #[cfg(feature = "decimal")]
+    pub fn from_decimal(value: Decimal) -> Number {
+        Number {
+            decimal: Some(value),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    pub fn get_decimal(&self) -> Option<Decimal> {
+        self.decimal
+    }
+
+    #[cfg(feature = "decimal")]
+    pub fn is_decimal(&self) -> bool {
+        self.decimal.is_some()
+    }
+
+    fn is_negative_decimal(&self) -> bool {
+        #[cfg(feature = "decimal")]
+        if let Some(decimal) = self.decimal {
+            return decimal.is_sign_negative() && !decimal.is_zero();
+        }
+        false
+    }
+
+    fn is_zero_decimal(&self) -> bool {
+        #[cfg(feature = "decimal")]
+        if let Some(decimal) = self.decimal {
+            return decimal.is_zero();
+        }
+        false
+    }
+
+    fn decimal_to_f64(&self) -> Option<f64> {
+        #[cfg(feature = "decimal")]
+        if let Some(decimal) = self.decimal {
+            return rust_decimal::prelude::ToPrimitive::to_f64(&decimal);
+        }
+        None
+    }
+
+    /// The number as a decimal, when that is exact: decimals and integers that fit.
+    #[cfg(feature = "decimal")]
+    fn exact_decimal(&self) -> Option<Decimal> {
+        match self.decimal {
+            Some(decimal) => Some(decimal),
+            None => self
+                .integer_as_i128()
+                .and_then(|value| Decimal::try_from_i128_with_scale(value, 0).ok()),
+        }
+    }
+
+    /// Parses a number like `Number::try_from`, but also accepts a leading `+` and
+    /// hexadecimal (`0x`), octal (`0o`) and binary (`0b`) integer prefixes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// assert_eq!(Number::parse_lenient("0xFF").unwrap().get_i32(), Some(255));
+    /// assert_eq!(Number::parse_lenient("+42").unwrap().get_i32(), Some(42));
+    /// assert!(Number::parse_lenient("0xZZ").is_err());
+    /// ```
+    pub fn parse_lenient(value: &str) -> Result<Number, Error> {
+        let value = value.trim();
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+
+        let radix = match unsigned.get(..2) {
+            Some("0x") | Some("0X") => 16,
+            Some("0o") | Some("0O") => 8,
+            Some("0b") | Some("0B") => 2,
+            _ => {
+                if unsigned.starts_with(['+', '-']) {
+                    return Err(Error::NotNumber);
+                }
+                return if negative {
+                    Number::try_from(value)
+                } else {
+                    Number::try_from(unsigned)
+                };
+            }
+        };
+
+        let digits = &unsigned[2..];
+        if digits.is_empty() || digits.starts_with(['+', '-']) {
+            return Err(Error::NotNumber);
+        }
+
+        let magnitude = u128::from_str_radix(digits, radix).map_err(|_| Error::NotNumber)?;
+        if negative {
+            let value = 0i128
+                .checked_sub_unsigned(magnitude)
+                .ok_or(Error::NotNumber)?;
+            Ok(match i32::try_from(value) {
+                Ok(value) => Number::from(value),
+                Err(_) => match i64::try_from(value) {
+                    Ok(value) => Number::from(value),
+                    Err(_) => Number::from(value),
+                },
+            })
+        } else if let Ok(value) = i32::try_from(magnitude) {
+            Ok(Number::from(value))
+        } else if let Ok(value) = i64::try_from(magnitude) {
+            Ok(Number::from(value))
+        } else if let Ok(value) = u64::try_from(magnitude) {
+            Ok(Number::from(value))
+        } else {
+            Ok(Number::from(magnitude))
+        }
+    }
+
+    /// Returns the stored integer widened to `i128`, or `None` for floats and
+    /// `u128` values above `i128::MAX`.
+    pub(crate) fn integer_as_i128(&self) -> Option<i128> {
+        if self.is_i8() {
+            Some(self.get_i8_unsafe() as i128)
+        } else if self.is_i16() {
+            Some(self.get_i16_unsafe() as i128)
+        } else if self.is_i32() {
+            Some(self.get_i32_unsafe() as i128)
+        } else if self.is_i64() {
+            Some(self.get_i64_unsafe() as i128)
+        } else if self.is_i128() {
+            Some(self.get_i128_unsafe())
+        } else if self.is_u8() {
+            Some(self.get_u8_unsafe() as i128)
+        } else if self.is_u16() {
+            Some(self.get_u16_unsafe() as i128)
+        } else if self.is_u32() {
+            Some(self.get_u32_unsafe() as i128)
+        } else if self.is_u64() {
+            Some(self.get_u64_unsafe() as i128)
+        } else if self.is_u128() {
+            i128::try_from(self.get_u128_unsafe()).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Adds two numbers. The sum stays an integer (`i64`, or `i128` when it does not
+    /// fit) when both operands are integers and it does not overflow; otherwise it is
+    /// computed as `f64`.
+    pub(crate) fn sum_with(&self, other: &Number) -> Number {
+        self.combine_with(other, ArithmeticOp::Add)
+    }
+
+    /// Checks if the stored number is an integer that can be narrowed to `i32` without loss.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let num = Number::from(u64::MAX);
+    /// assert_eq!(num.fits_in_i32(), false);
+    /// ```
+    pub fn fits_in_i32(&self) -> bool {
+        match self.integer_as_i128() {
+            Some(value) => i32::try_from(value).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Checks if the stored number is an integer that can be narrowed to `u32` without loss.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let num = Number::from(-1i64);
+    /// assert_eq!(num.fits_in_u32(), false);
+    /// ```
+    pub fn fits_in_u32(&self) -> bool {
+        match self.integer_as_i128() {
+            Some(value) => u32::try_from(value).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Checks if the stored number can be represented as an `f32` without rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let num = Number::from(16_777_217u32);
+    /// assert_eq!(num.fits_in_f32_exactly(), false);
+    /// ```
+    pub fn fits_in_f32_exactly(&self) -> bool {
+        if self.is_f32() {
+            return true;
+        }
+
+        if self.is_f64() {
+            let value = self.get_f64_unsafe();
+            return value.is_nan() || (value as f32) as f64 == value;
+        }
+
+        if self.is_u128() {
+            return integer_fits_in_f32(self.get_u128_unsafe());
+        }
+
+        match self.integer_as_i128() {
+            Some(value) => integer_fits_in_f32(value.unsigned_abs()),
+            None => false,
+        }
+    }
+
+    /// Applies an arithmetic operation. Two integers give an integer unless the result
+    /// overflows or, for division, is not exact. With the `decimal` feature, a decimal
+    /// combined with a decimal or an integer gives an exact decimal. Every other case is
+    /// computed as `f64`.
+    pub(crate) fn combine_with(&self, other: &Number, op: ArithmeticOp) -> Number {
+        if let (Some(a), Some(b)) = (self.integer_as_i128(), other.integer_as_i128()) {
+            if let Some(result) = op.integer(a, b) {
+                return match i64::try_from(result) {
+                    Ok(result) => Number::from(result),
+                    Err(_) => Number::from(result),
+                };
+            }
+        }
+        #[cfg(feature = "decimal")]
+        if self.is_decimal() || other.is_decimal() {
+            if let (Some(a), Some(b)) = (self.exact_decimal(), other.exact_decimal()) {
+                if let Some(result) = op.decimal(a, b) {
+                    return Number::from_decimal(result.normalize());
+                }
+            }
+        }
+        Number::from(op.float(self.to_f64().unwrap_or(0.0), other.to_f64().unwrap_or(0.0)))
+    }
 }
 
 // Implementations of methods for setting and getting number values safely and unsafely,
@@ -441,6 +728,10 @@ impl NumberBehavior for Number {
     }
 
     fn is_number(&self) -> bool {
+        #[cfg(feature = "decimal")]
+        if self.is_decimal() {
+            return true;
+        }
         self.is_i8()
             || self.is_i16()
             || self.is_i32()
@@ -493,6 +784,7 @@ impl NumberBehavior for Number {
             || self.is_i128() && self.i128.unwrap() < 0
             || self.is_f32() && self.f32.unwrap() < 0.0
             || self.is_f64() && self.f64.unwrap() < 0.0
+            || self.is_negative_decimal()
     }
 
     fn is_unsigned(&self) -> bool {
@@ -512,6 +804,7 @@ impl NumberBehavior for Number {
             || self.is_u32() && self.u32.unwrap() == 0
             || self.is_u64() && self.u64.unwrap() == 0
             || self.is_u128() && self.u128.unwrap() == 0
+            || self.is_zero_decimal()
     }
 
     fn is_positive(&self) -> bool {
@@ -548,6 +841,10 @@ impl NumberBehavior for Number {
         } else if self.is_f64() {
             NumberType::F64
         } else {
+            #[cfg(feature = "decimal")]
+            if self.is_decimal() {
+                return NumberType::Decimal;
+            }
             NumberType::Unknown
         }
     }
@@ -578,7 +875,7 @@ impl NumberBehavior for Number {
         } else if self.is_u8() {
             Some(self.get_u8_unsafe() as f64)
         } else {
-            None
+            self.decimal_to_f64()
         }
     }
 
@@ -715,6 +1012,10 @@ impl Display for Number {
         } else if self.is_f64() {
             write!(f, "{}", self.get_f64_unsafe())
         } else {
+            #[cfg(feature = "decimal")]
+            if let Some(decimal) = self.decimal {
+                return write!(f, "{}", decimal);
+            }
             write!(f, "0")
         }
     }
@@ -977,6 +1278,13 @@ impl TryFrom<String> for Number {
     }
 }
 
+/// An integer is exact in `f32` when its significant bits fit the 24-bit mantissa. Every
+/// `u128` magnitude is below `f32::MAX`, so only the mantissa width matters; the cast
+/// round trip can't be used because float-to-int casts saturate.
+fn integer_fits_in_f32(magnitude: u128) -> bool {
+    magnitude == 0 || magnitude >> magnitude.trailing_zeros() < 1 << f32::MANTISSA_DIGITS
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -1195,6 +1503,56 @@ mod tests {
         assert_eq!(number.number_type(), NumberType::F64);
     }
 
+    #[test]
+    fn test_fits_in() {
+        let number = Number::from(u64::MAX);
+        assert!(number.is_integer());
+        assert!(!number.fits_in_i32());
+        assert!(!number.fits_in_u32());
+
+        let number = Number::from(-42i64);
+        assert!(number.fits_in_i32());
+        assert!(!number.fits_in_u32());
+
+        let number = Number::from(42u128);
+        assert!(number.fits_in_i32());
+        assert!(number.fits_in_u32());
+
+        let number = Number::from(2.5);
+        assert!(number.is_float());
+        assert!(!number.is_integer());
+        assert!(!number.fits_in_i32());
+        assert!(number.fits_in_f32_exactly());
+
+        assert!(!Number::from(0.1f64).fits_in_f32_exactly());
+        assert!(Number::from(16_777_216u32).fits_in_f32_exactly());
+        assert!(!Number::from(16_777_217u32).fits_in_f32_exactly());
+        assert!(!Number::from(u128::MAX).fits_in_f32_exactly());
+        assert!(!Number::from(i128::MAX).fits_in_f32_exactly());
+        assert!(Number::from(i128::MIN).fits_in_f32_exactly());
+        assert!(Number::from(1u128 << 127).fits_in_f32_exactly());
+        assert!(!Number::from(u32::MAX).fits_in_f32_exactly());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        assert_eq!(Number::parse_lenient("+42"), Ok(Number::from(42)));
+        assert_eq!(Number::parse_lenient("0xFF"), Ok(Number::from(255)));
+        assert_eq!(Number::parse_lenient("0o17"), Ok(Number::from(15)));
+        assert_eq!(Number::parse_lenient("0b1010"), Ok(Number::from(10)));
+        assert_eq!(Number::parse_lenient("-0x10"), Ok(Number::from(-16)));
+        assert_eq!(Number::parse_lenient("+1.5"), Ok(Number::from(1.5)));
+        assert_eq!(
+            Number::parse_lenient("0xFFFFFFFFFF"),
+            Ok(Number::from(0xFFFFFFFFFFi64))
+        );
+
+        assert_eq!(Number::parse_lenient("0xZZ"), Err(Error::NotNumber));
+        assert_eq!(Number::parse_lenient("0x"), Err(Error::NotNumber));
+        assert_eq!(Number::parse_lenient("+-1"), Err(Error::NotNumber));
+        assert!(Number::try_from("+0x1").is_err());
+    }
+
     #[test]
     fn test_from_usize() {
         let number = Number::from(42usize);
@@ -1263,4 +1621,28 @@ mod tests {
         number.clean().set_u128(u128::MAX);
         assert_eq!(number.to_u64(), None);
     }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_arithmetic_is_exact() {
+        use std::str::FromStr;
+
+        let decimal =
+            |raw: &str| Value::Number(Number::from_decimal(Decimal::from_str(raw).unwrap()));
+
+        assert_ne!(0.1f64 + 0.2f64, 0.3f64);
+        let sum = decimal("0.1") + decimal("0.2");
+        assert_eq!(sum, decimal("0.3"));
+        assert_eq!(sum.to_string(), "0.3");
+
+        let total = decimal("19.99") * Value::from(3) - decimal("0.97");
+        assert_eq!(total.to_string(), "59");
+        assert_eq!(decimal("1") / decimal("8"), decimal("0.125"));
+        assert_eq!(decimal("2.5") + Value::from(0.5), Value::from(3.0));
+
+        let number = Number::from_decimal(Decimal::from_str("-1.50").unwrap());
+        assert!(number.is_number() && number.is_negative());
+        assert_eq!(number.number_type(), NumberType::Decimal);
+        assert_eq!(number.to_f64(), Some(-1.5));
+    }
 }
@@ -27,6 +27,22 @@ pub enum Object {
     HashMap(HashMap<ValueKey, Value>),
 }
 
+/// Builds an object in the default backing store: a `HashMap`, or a `BTreeMap` with
+/// sorted key iteration when the `sorted-map` feature is enabled.
+fn default_backing<I>(entries: I) -> Object
+where
+    I: IntoIterator<Item = (ValueKey, Value)>,
+{
+    #[cfg(feature = "sorted-map")]
+    {
+        Object::BTreeMap(entries.into_iter().collect())
+    }
+    #[cfg(not(feature = "sorted-map"))]
+    {
+        Object::HashMap(entries.into_iter().collect())
+    }
+}
+
 impl PartialOrd for Object {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
@@ -38,6 +54,21 @@ impl PartialOrd for Object {
 }
 
 impl Object {
+    /// Creates an empty object whose backing map can hold `capacity` entries without
+    /// reallocating. With the `sorted-map` feature the `BTreeMap` store has no capacity
+    /// to reserve, so this is the same as `Object::default()`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        #[cfg(feature = "sorted-map")]
+        {
+            let _ = capacity;
+            Object::default()
+        }
+        #[cfg(not(feature = "sorted-map"))]
+        {
+            Object::HashMap(HashMap::with_capacity(capacity))
+        }
+    }
+
     /// Returns a reference to the value associated with the specified key, or `None` if the key is not present.
     pub fn get<T>(&self, key: T) -> Option<&Value>
     where
@@ -93,6 +124,22 @@ impl Object {
             Object::HashMap(map) => map.is_empty(),
         }
     }
+
+    /// Consumes the object into its entries, sorted by key so the order does not depend
+    /// on the backing map.
+    pub fn into_entries(self) -> Vec<(String, Value)> {
+        let mut entries: Vec<(String, Value)> = match self {
+            Object::BTreeMap(map) => map.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+            Object::HashMap(map) => map.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        };
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        entries
+    }
+
+    /// Builds an object in the default backing store. When a key repeats, the last entry wins.
+    pub fn from_entries(entries: Vec<(String, Value)>) -> Self {
+        default_backing(entries.into_iter().map(|(k, v)| (k.to_value_key(), v)))
+    }
 }
 
 impl ObjectBehavior for Object {
@@ -134,9 +181,9 @@ impl ObjectBehavior for Object {
 }
 
 impl Default for Object {
-    /// Creates a new `Object` with an empty `HashMap`.
+    /// Creates a new empty `Object` in the default backing store.
     fn default() -> Self {
-        Object::HashMap(HashMap::new())
+        default_backing(std::iter::empty())
     }
 }
 
@@ -163,26 +210,28 @@ where
 {
     /// Converts BTreeMap<ValueKey, Value> into Object.
     fn from(value: HashMap<T, V>) -> Self {
-        Object::HashMap(
-            value
-                .iter()
-                .map(|(k, v)| (k.to_value_key(), v.to_value()))
-                .collect::<HashMap<ValueKey, Value>>(),
-        )
+        default_backing(value.iter().map(|(k, v)| (k.to_value_key(), v.to_value())))
     }
 }
 
 impl From<HashMap<ValueKey, Value>> for Object {
     /// Converts HashMap<ValueKey, Value> into Object.
     fn from(value: HashMap<ValueKey, Value>) -> Self {
-        Object::HashMap(value)
+        #[cfg(feature = "sorted-map")]
+        {
+            default_backing(value)
+        }
+        #[cfg(not(feature = "sorted-map"))]
+        {
+            Object::HashMap(value)
+        }
     }
 }
 
 impl From<Vec<(ValueKey, Value)>> for Object {
     /// Converts a vector of key-value pairs into an Object.
     fn from(value: Vec<(ValueKey, Value)>) -> Self {
-        Object::HashMap(value.into_iter().collect())
+        default_backing(value)
     }
 }
 
@@ -193,11 +242,10 @@ where
 {
     /// Converts a vector of key-value pairs into an Object.
     fn from(value: Vec<(T, V)>) -> Self {
-        Object::HashMap(
+        default_backing(
             value
                 .into_iter()
-                .map(|(k, v)| (k.to_value_key(), v.to_value()))
-                .collect(),
+                .map(|(k, v)| (k.to_value_key(), v.to_value())),
         )
     }
 }
@@ -303,4 +351,67 @@ mod tests {
         assert_eq!(obj.get("key1"), Some(&Value::Null));
         assert_eq!(obj.get("key2"), Some(&StringB::from("ok").to_value()));
     }
+
+    #[test]
+    fn test_object_with_capacity() {
+        let mut obj = Object::with_capacity(100);
+        assert!(obj.is_empty());
+
+        #[cfg(not(feature = "sorted-map"))]
+        if let Object::HashMap(map) = &obj {
+            assert!(map.capacity() >= 100);
+        }
+
+        for i in 0..100 {
+            obj.insert(format!("key_{}", i), Value::from(i));
+        }
+        assert_eq!(obj.len(), 100);
+        assert_eq!(obj.get("key_42"), Some(&Value::from(42)));
+    }
+
+    #[test]
+    fn test_object_entries_round_trip() {
+        let mut object = Object::default();
+        object.insert("b", Value::from(2));
+        object.insert("a", Value::from(1));
+        object.insert("c", Value::Null);
+
+        let mut entries = object.clone().into_entries();
+        assert_eq!(
+            entries,
+            vec![
+                ("a".to_string(), Value::from(1)),
+                ("b".to_string(), Value::from(2)),
+                ("c".to_string(), Value::Null),
+            ]
+        );
+        assert_eq!(Object::from_entries(entries.clone()), object);
+
+        entries.reverse();
+        entries.push(("a".to_string(), Value::from(10)));
+        let rebuilt = Object::from_entries(entries);
+        assert_eq!(rebuilt.get("a"), Some(&Value::from(10)));
+        assert_eq!(rebuilt.len(), 3);
+    }
+
+    #[cfg(feature = "sorted-map")]
+    #[test]
+    fn test_sorted_map_key_order() {
+        let mut map = HashMap::new();
+        for key in ["delta", "alpha", "charlie", "bravo"] {
+            map.insert(key.to_string(), Value::from(1));
+        }
+
+        let value = Object::from(map).to_value();
+        assert_eq!(
+            value.to_json(JsonMode::Inline),
+            r#"{"alpha":1,"bravo":1,"charlie":1,"delta":1}"#
+        );
+
+        let mut object = Object::default();
+        object.insert("b", Value::Null);
+        object.insert("a", Value::Null);
+        let keys: Vec<String> = object.keys().iter().map(|k| k.to_string()).collect();
+        assert_eq!(keys, vec!["a", "b"]);
+    }
 }
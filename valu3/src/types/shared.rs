@@ -0,0 +1,223 @@
+use crate::impls::unescape_pointer_token;
+use crate::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// A reference-counted, copy-on-write handle to a `Value` tree.
+///
+/// Every array and object in the tree is reference-counted on its own, so cloning a
+/// `SharedValue` only bumps a counter and one tree can be handed to many threads or
+/// owners cheaply. A write through `pointer_mut` copies just the containers on the
+/// path to the edited node; untouched sibling subtrees stay shared between handles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedValue(Arc<SharedNode>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum SharedNode {
+    Scalar(Value),
+    Array(Vec<SharedValue>),
+    BTreeMap(BTreeMap<ValueKey, SharedValue>),
+    HashMap(HashMap<ValueKey, SharedValue>),
+}
+
+impl SharedValue {
+    pub fn new(value: Value) -> Self {
+        let node = match value {
+            Value::Array(array) => {
+                SharedNode::Array(array.values.into_iter().map(SharedValue::new).collect())
+            }
+            Value::Object(Object::BTreeMap(map)) => SharedNode::BTreeMap(
+                map.into_iter()
+                    .map(|(key, value)| (key, SharedValue::new(value)))
+                    .collect(),
+            ),
+            Value::Object(Object::HashMap(map)) => SharedNode::HashMap(
+                map.into_iter()
+                    .map(|(key, value)| (key, SharedValue::new(value)))
+                    .collect(),
+            ),
+            scalar => SharedNode::Scalar(scalar),
+        };
+        SharedValue(Arc::new(node))
+    }
+
+    /// Returns the value if this node is a scalar, or `None` for arrays and objects.
+    pub fn as_scalar(&self) -> Option<&Value> {
+        match self.0.as_ref() {
+            SharedNode::Scalar(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Looks up a node by JSON Pointer (RFC 6901), as `Value::pointer` does.
+    pub fn pointer(&self, pointer: &str) -> Option<&SharedValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .try_fold(self, |target, token| match target.0.as_ref() {
+                SharedNode::Array(items) => token.parse::<usize>().ok().and_then(|i| items.get(i)),
+                SharedNode::BTreeMap(map) => map.get(&token.as_str().to_value_key()),
+                SharedNode::HashMap(map) => map.get(&token.as_str().to_value_key()),
+                SharedNode::Scalar(_) => None,
+            })
+    }
+
+    /// Looks up a node by JSON Pointer for writing. Each container on the path that other
+    /// handles still share is copied first; its children are not.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut SharedValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape_pointer_token)
+            .try_fold(self, |target, token| match Arc::make_mut(&mut target.0) {
+                SharedNode::Array(items) => token
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(move |i| items.get_mut(i)),
+                SharedNode::BTreeMap(map) => map.get_mut(&token.as_str().to_value_key()),
+                SharedNode::HashMap(map) => map.get_mut(&token.as_str().to_value_key()),
+                SharedNode::Scalar(_) => None,
+            })
+    }
+
+    /// Replaces this node, leaving other handles to the old one untouched.
+    pub fn set(&mut self, value: Value) {
+        *self = SharedValue::new(value);
+    }
+
+    /// Returns true if both handles point to the same underlying node.
+    pub fn ptr_eq(&self, other: &SharedValue) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Rebuilds the value, copying only the nodes other handles still share.
+    pub fn into_value(self) -> Value {
+        let node = Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone());
+        match node {
+            SharedNode::Scalar(value) => value,
+            SharedNode::Array(items) => Value::from(
+                items
+                    .into_iter()
+                    .map(SharedValue::into_value)
+                    .collect::<Vec<_>>(),
+            ),
+            SharedNode::BTreeMap(map) => Value::Object(Object::BTreeMap(
+                map.into_iter()
+                    .map(|(key, value)| (key, value.into_value()))
+                    .collect(),
+            )),
+            SharedNode::HashMap(map) => Value::Object(Object::HashMap(
+                map.into_iter()
+                    .map(|(key, value)| (key, value.into_value()))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl ToValueBehavior for SharedValue {
+    fn to_value(&self) -> Value {
+        self.clone().into_value()
+    }
+}
+
+impl From<Value> for SharedValue {
+    fn from(value: Value) -> Self {
+        SharedValue::new(value)
+    }
+}
+
+impl Value {
+    /// Copies the value once into a `SharedValue`, whose clones are O(1).
+    ///
+    /// This returns a `SharedValue` rather than a `Value`: making `Value` itself
+    /// reference-counted would change the public `Array` and `Object` types for every
+    /// user. Use `to_value` or `into_value` to get a plain `Value` back.
+    pub fn share(&self) -> SharedValue {
+        SharedValue::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_copy_on_write() {
+        let value = Value::from(vec![
+            ("name", Value::from("service")),
+            ("ports", Value::from(vec![80, 443])),
+        ]);
+
+        let shared = value.share();
+        let mut copy = shared.clone();
+        assert_eq!(shared.to_value(), value);
+        assert!(copy.ptr_eq(&shared));
+
+        copy.pointer_mut("/name")
+            .unwrap()
+            .set(Value::from("worker"));
+        assert!(!copy.ptr_eq(&shared));
+        assert_eq!(
+            shared.pointer("/name").and_then(SharedValue::as_scalar),
+            Some(&"service".to_value())
+        );
+        assert_eq!(
+            copy.pointer("/name").and_then(SharedValue::as_scalar),
+            Some(&"worker".to_value())
+        );
+
+        // The untouched sibling is still the same node in both handles.
+        let ports = shared.pointer("/ports").unwrap();
+        assert!(copy.pointer("/ports").unwrap().ptr_eq(ports));
+        assert_eq!(
+            ports.pointer("/1").and_then(SharedValue::as_scalar),
+            Some(&443.to_value())
+        );
+
+        assert_eq!(shared.into_value(), value);
+        assert_eq!(copy.into_value().get("name"), Some(&"worker".to_value()));
+    }
+
+    #[test]
+    fn test_share_copies_only_the_written_path() {
+        let value = Value::from(vec![
+            (
+                "a",
+                Value::from(vec![("x", Value::from(1)), ("y", Value::from(vec![1]))]),
+            ),
+            ("b", Value::from(vec![2])),
+        ]);
+        let shared = value.share();
+        let mut copy = shared.clone();
+
+        copy.pointer_mut("/a/x").unwrap().set(Value::from(2));
+
+        let same = |pointer: &str| {
+            copy.pointer(pointer)
+                .unwrap()
+                .ptr_eq(shared.pointer(pointer).unwrap())
+        };
+        assert!(!same("/a"));
+        assert!(!same("/a/x"));
+        assert!(same("/a/y"));
+        assert!(same("/b"));
+        assert_eq!(shared.to_value(), value);
+        assert_eq!(copy.to_value().pointer("/a/x"), Some(&2.to_value()));
+    }
+}
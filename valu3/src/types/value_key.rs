@@ -16,6 +16,14 @@ impl ValueKey {
         }
     }
 
+    /// Borrows a string key. Returns `None` for numeric keys.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ValueKey::String(s) => Some(s.as_str()),
+            ValueKey::Number(_) => None,
+        }
+    }
+
     pub fn to_usize(&self) -> usize {
         match self {
             ValueKey::String(s) => panic!("Cannot convert string to usize: {}", s),
@@ -3,7 +3,7 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, Data, DeriveInput, Fields, Generics, Variant};
 
-#[proc_macro_derive(ToValue, attributes(attr))]
+#[proc_macro_derive(ToValue, attributes(attr, valu3))]
 pub fn to_value_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -12,22 +12,25 @@ pub fn to_value_derive(input: TokenStream) -> TokenStream {
 
     let to_value_impl = match input.data {
         Data::Struct(data) => to_value_struct_impl(name, generics, data.fields),
-        Data::Enum(data) => to_value_enum_impl(name, generics, data.variants),
-        Data::Union(_) => panic!("ToValueBehavior cannot be derived for unions"),
+        Data::Enum(data) => valu3_repr_index(&input.attrs)
+            .and_then(|repr_index| to_value_enum_impl(name, generics, data.variants, repr_index)),
+        Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "ToValue cannot be derived for unions; use a struct or an enum",
+        )),
     };
 
-    let expanded = quote! {
-        #to_value_impl
-    };
-
-    TokenStream::from(expanded)
+    match to_value_impl {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
 }
 
 fn to_value_struct_impl(
     name: syn::Ident,
     generics: Generics,
     fields: Fields,
-) -> proc_macro2::TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let field_transforms = match fields {
@@ -40,34 +43,55 @@ fn to_value_struct_impl(
                     None => panic!("ToValueBehavior cannot be derived for unnamed fields"),
                 };
                 let field_name = format!("{}", name);
-                quote! {
-                    map.insert(#field_name.to_string(), self.#name.clone().into());
-                }
+                Ok(match valu3_with(&field.attrs)? {
+                    Some(module) => quote! {
+                        map.insert(#field_name.to_string(), #module::to_value(&self.#name));
+                    },
+                    None => quote! {
+                        map.insert(#field_name.to_string(), self.#name.clone().into());
+                    },
+                })
             })
-            .collect::<Vec<_>>(),
-        Fields::Unnamed(fields) => fields
-            .unnamed
-            .iter()
-            .enumerate()
-            .map(|(index, _field)| {
-                let index = syn::Index::from(index);
-                quote! {
-                    Value::from(self.#index.clone())
+            .collect::<syn::Result<Vec<_>>>()?,
+        Fields::Unnamed(fields) => {
+            // Tuple structs are represented positionally, as an array.
+            let items = fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let index = syn::Index::from(index);
+                    Ok(match valu3_with(&field.attrs)? {
+                        Some(module) => quote! {
+                            #module::to_value(&self.#index)
+                        },
+                        None => quote! {
+                            self.#index.to_value()
+                        },
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            return Ok(quote! {
+                impl #impl_generics ToValueBehavior for #name #ty_generics #where_clause {
+                    fn to_value(&self) -> Value {
+                        Value::from(vec![#(#items),*])
+                    }
                 }
-            })
-            .collect::<Vec<_>>(),
+            });
+        }
         Fields::Unit => {
-            return quote! {
+            return Ok(quote! {
                 impl #impl_generics ToValueBehavior for  #name #ty_generics #where_clause {
                     fn to_value(&self) -> Value {
                         Value::Null
                     }
                 }
-            }
+            })
         }
     };
 
-    quote! {
+    Ok(quote! {
         impl #impl_generics ToValueBehavior  for #name #ty_generics #where_clause {
             fn to_value(&self) -> Value {
                 let mut map: std::collections::HashMap<String, Value>= std::collections::HashMap::new();
@@ -75,32 +99,39 @@ fn to_value_struct_impl(
                 Value::from(map)
             }
         }
-    }
+    })
 }
 
 fn to_value_enum_impl(
     name: syn::Ident,
     generics: Generics,
     variants: syn::punctuated::Punctuated<Variant, syn::Token![,]>,
-) -> proc_macro2::TokenStream {
+    repr_index: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     // Ensure we only support unit variants for now. If a variant carries data,
-    // fail early with a clear message so the user knows the derive is not
+    // fail early with an error pointing at it so the user knows the derive is not
     // implemented for data-carrying variants yet.
     let mut arms = Vec::new();
-    for variant in variants.iter() {
+    for (index, variant) in variants.iter().enumerate() {
         let variant_name = &variant.ident;
         match &variant.fields {
+            Fields::Unit if repr_index => {
+                let index = index as u64;
+                arms.push(quote! {
+                    #name::#variant_name => Value::from(#index),
+                });
+            }
             Fields::Unit => {
                 arms.push(quote! {
                     #name::#variant_name => Value::from(stringify!(#variant_name)),
                 });
             }
-            _ => panic!("ToValue cannot be derived for enums with data-carrying variants (tuple or struct variants)"),
+            _ => return Err(unit_variants_only("ToValue", variant)),
         }
     }
 
-    quote! {
+    Ok(quote! {
         impl #impl_generics ToValueBehavior for #name #ty_generics #where_clause {
             fn to_value(&self) -> Value {
                 match self {
@@ -108,14 +139,92 @@ fn to_value_enum_impl(
                 }
             }
         }
+    })
+}
+
+/// The error for a tuple or struct variant, which the enum derives cannot represent yet.
+fn unit_variants_only(derive: &str, variant: &Variant) -> syn::Error {
+    syn::Error::new_spanned(
+        variant,
+        format!(
+            "{} can only be derived for enums whose variants are all unit variants; `{}` carries data",
+            derive, variant.ident
+        ),
+    )
+}
+
+/// Collects the arguments listed in `#[valu3(...)]` attributes, either bare flags such as
+/// `#[valu3(deny_unknown_fields)]` or string options such as `#[valu3(with = "codec")]`.
+fn valu3_args(attrs: &[syn::Attribute]) -> syn::Result<Vec<(syn::Ident, Option<syn::LitStr>)>> {
+    let mut args = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("valu3")) {
+        attr.parse_nested_meta(|meta| match meta.path.get_ident() {
+            Some(ident) => {
+                let value = if meta.input.peek(syn::Token![=]) {
+                    Some(meta.value()?.parse::<syn::LitStr>()?)
+                } else {
+                    None
+                };
+                args.push((ident.clone(), value));
+                Ok(())
+            }
+            None => Err(meta.error("expected an identifier")),
+        })?;
     }
+    Ok(args)
 }
 
-#[proc_macro_derive(FromValue)]
+/// Returns true for `#[valu3(repr = "index")]`, which represents unit enum variants by
+/// their position (`0`, `1`, ...) instead of their name.
+fn valu3_repr_index(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    match valu3_args(attrs)?
+        .into_iter()
+        .find(|(name, _)| name == "repr")
+    {
+        Some((_, Some(repr))) if repr.value() == "index" => Ok(true),
+        Some((_, Some(repr))) => Err(syn::Error::new_spanned(
+            &repr,
+            format!(
+                "Unsupported valu3 repr: {}; the only supported repr is \"index\"",
+                repr.value()
+            ),
+        )),
+        Some((name, None)) => Err(syn::Error::new_spanned(
+            name,
+            "valu3 repr expects a value, e.g. #[valu3(repr = \"index\")]",
+        )),
+        None => Ok(false),
+    }
+}
+
+/// Returns the module named by `#[valu3(with = "module")]`, whose `to_value(&T) -> Value`
+/// and `from_value(Value) -> Option<T>` functions replace the field's default conversion.
+fn valu3_with(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Path>> {
+    match valu3_args(attrs)?
+        .into_iter()
+        .find(|(name, _)| name == "with")
+    {
+        Some((_, Some(value))) => value.parse::<syn::Path>().map(Some),
+        Some((name, None)) => Err(syn::Error::new_spanned(
+            name,
+            "valu3 with expects a module path, e.g. #[valu3(with = \"codec\")]",
+        )),
+        None => Ok(None),
+    }
+}
+
+#[proc_macro_derive(FromValue, attributes(valu3))]
 pub fn from_value_derive(input: TokenStream) -> TokenStream {
     // Parse a `DeriveInput` AST from the input tokens.
     let ast = parse_macro_input!(input as DeriveInput);
 
+    match from_value_impl(ast) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn from_value_impl(ast: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     // Get the name and fields of the struct being derived.
     let target_name = &ast.ident;
     let target_generics = &ast.generics;
@@ -127,6 +236,68 @@ pub fn from_value_derive(input: TokenStream) -> TokenStream {
             let mut field_names = Vec::new();
             let mut from_value_exprs = Vec::new();
 
+            let mut deny_unknown_fields = false;
+            for (flag, _) in valu3_args(&ast.attrs)? {
+                match flag.to_string().as_str() {
+                    "deny_unknown_fields" => deny_unknown_fields = true,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            target_name,
+                            format!("Unsupported valu3 container attribute: {}", flag),
+                        ))
+                    }
+                }
+            }
+
+            if let Fields::Unnamed(fields) = &data_struct.fields {
+                if deny_unknown_fields {
+                    return Err(syn::Error::new_spanned(
+                        fields,
+                        "deny_unknown_fields is not supported on tuple structs",
+                    ));
+                }
+
+                let len = fields.unnamed.len();
+                let items = fields
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        let field_type = &field.ty;
+                        let convert = match valu3_with(&field.attrs)? {
+                            Some(module) => quote! { #module::from_value },
+                            None => quote! { <#field_type as FromValueBehavior>::from_value },
+                        };
+                        Ok(quote! {
+                            match array.get(#index) {
+                                Some(item) => match #convert(item.clone()) {
+                                    Some(item) => item,
+                                    None => return None,
+                                },
+                                None => return None,
+                            }
+                        })
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+
+                let expanded = quote! {
+                    impl #impl_generics FromValueBehavior for #target_name #ty_generics #where_clause {
+                        type Item = Self;
+
+                        fn from_value(value: Value) -> Option<Self> {
+                            match value {
+                                Value::Array(array) if array.len() == #len => {
+                                    Some(Self(#(#items),*))
+                                }
+                                _ => None,
+                            }
+                        }
+                    }
+                };
+
+                return Ok(expanded);
+            }
+
             if let Fields::Named(fields) = data_struct.fields {
                 for field in fields.named.iter() {
                     let field_name = match field.ident.as_ref() {
@@ -139,13 +310,39 @@ pub fn from_value_derive(input: TokenStream) -> TokenStream {
 
                     field_names.push(field_name.clone());
 
+                    let mut skip_deserializing = false;
+                    for (flag, _) in valu3_args(&field.attrs)? {
+                        match flag.to_string().as_str() {
+                            "skip_deserializing" => skip_deserializing = true,
+                            "with" => {}
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    field,
+                                    format!("Unsupported valu3 field attribute: {}", flag),
+                                ))
+                            }
+                        }
+                    }
+
+                    if skip_deserializing {
+                        from_value_exprs.push(quote! {
+                            #field_name: Default::default()
+                        });
+                        continue;
+                    }
+
+                    let convert = match valu3_with(&field.attrs)? {
+                        Some(module) => quote! { #module::from_value },
+                        None => quote! { <#field_type as FromValueBehavior>::from_value },
+                    };
+
                     from_value_exprs.push(quote! {
                         #field_name: {
                             let item = match map.get(stringify!(#field_name)) {
                                 Some(item) => item.clone(),
                                 None => return None,
                             };
-                            match <#field_type as FromValueBehavior>::from_value(item) {
+                            match #convert(item) {
                                 Some(item) => item,
                                 None => return None,
                             }
@@ -153,15 +350,34 @@ pub fn from_value_derive(input: TokenStream) -> TokenStream {
                     });
                 }
             } else {
-                panic!("Can only derive FromValueBehavior for a struct with named fields.");
+                return Err(syn::Error::new_spanned(
+                    target_name,
+                    "FromValue cannot be derived for unit structs; add named or tuple fields",
+                ));
             }
 
+            let unknown_fields_check = if deny_unknown_fields {
+                quote! {
+                    const FIELDS: &[&str] = &[#(stringify!(#field_names)),*];
+                    if map
+                        .keys()
+                        .iter()
+                        .any(|key| !FIELDS.contains(&key.to_string().as_str()))
+                    {
+                        return None;
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
             let expanded = quote! {
                 impl #impl_generics FromValueBehavior for #target_name #ty_generics #where_clause {
                     type Item = Self;
 
                     fn from_value(value: Value) -> Option<Self> {
                         if let Value::Object(map) = value {
+                            #unknown_fields_check
                             Some(
                                 Self {
                                     #(#from_value_exprs),*
@@ -174,10 +390,11 @@ pub fn from_value_derive(input: TokenStream) -> TokenStream {
                 }
             };
 
-            TokenStream::from(expanded)
+            Ok(expanded)
         }
         Data::Enum(data_enum) => {
             let variants = data_enum.variants;
+            let repr_index = valu3_repr_index(&ast.attrs)?;
 
             let mut variant_names = Vec::new();
 
@@ -189,10 +406,39 @@ pub fn from_value_derive(input: TokenStream) -> TokenStream {
                         let variant_name = &variant.ident;
                         variant_names.push(variant_name.clone());
                     }
-                    _ => panic!("FromValue cannot be derived for enums with data-carrying variants (tuple or struct variants)"),
+                    _ => return Err(unit_variants_only("FromValue", variant)),
                 }
             }
 
+            let from_value_body = if repr_index {
+                let indices = (0..variant_names.len() as u64).collect::<Vec<_>>();
+                quote! {
+                    match value {
+                        Value::Number(number) => match number.to_u64() {
+                            #(
+                                Some(#indices) => Some(#target_name::#variant_names),
+                            )*
+                            _ => None,
+                        },
+                        _ => None,
+                    }
+                }
+            } else {
+                quote! {
+                    match value {
+                        Value::String(value) => {
+                            match value.as_str() {
+                                #(
+                                    v if v == stringify!(#variant_names) => Some(#target_name::#variant_names),
+                                )*
+                                _ => None,
+                            }
+                        },
+                        _ => None,
+                    }
+                }
+            };
+
             let expanded = quote! {
                 impl #impl_generics PrimitiveType for #target_name #ty_generics #where_clause {}
 
@@ -200,24 +446,17 @@ pub fn from_value_derive(input: TokenStream) -> TokenStream {
                     type Item = Self;
 
                     fn from_value(value: Value) -> Option<Self> {
-                        match value {
-                            Value::String(value) => {
-                                match value.as_str() {
-                                    #(
-                                        v if v == stringify!(#variant_names) => Some(#target_name::#variant_names),
-                                    )*
-                                    _ => None,
-                                }
-                            },
-                            _ => None,
-                        }
+                        #from_value_body
                     }
                 }
             };
 
-            TokenStream::from(expanded)
+            Ok(expanded)
         }
-        _ => panic!("Can only derive FromValueBehavior for a struct."),
+        Data::Union(data_union) => Err(syn::Error::new_spanned(
+            data_union.union_token,
+            "FromValue cannot be derived for unions; use a struct or an enum",
+        )),
     }
 }
 
@@ -257,3 +496,103 @@ pub fn to_yaml_derive(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Builds a `Value` from JSON-like syntax, e.g. `value!({ "a": [1, null], "b": if x { 1 } else { 2 } })`.
+///
+/// Unlike the declarative `json!` macro, the input is parsed with `syn`, so every value
+/// position accepts any Rust expression (closures, ranges, `if`, `match`, ...). A `{...}`,
+/// `[...]` or `null` only counts as JSON syntax when it forms the whole value; anything
+/// else is an expression converted with `to_value()`. String-literal keys are used as-is,
+/// other keys are expressions converted with `to_string()`.
+#[proc_macro]
+pub fn value(input: TokenStream) -> TokenStream {
+    use syn::parse::Parser;
+
+    match value_macro_value.parse(input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+/// Returns true when the next token tree is followed by a comma or the end of input.
+fn value_macro_is_whole(input: syn::parse::ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<proc_macro2::TokenTree>().is_ok() && (fork.is_empty() || fork.peek(syn::Token![,]))
+}
+
+fn value_macro_value(input: syn::parse::ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    use syn::parse::Parser;
+
+    if input.peek(syn::token::Brace) && value_macro_is_whole(input) {
+        let group: proc_macro2::Group = input.parse()?;
+        return value_macro_object.parse2(group.stream());
+    }
+
+    if input.peek(syn::token::Bracket) && value_macro_is_whole(input) {
+        let group: proc_macro2::Group = input.parse()?;
+        return value_macro_array.parse2(group.stream());
+    }
+
+    if input.peek(syn::Ident) && value_macro_is_whole(input) {
+        let fork = input.fork();
+        let ident: syn::Ident = fork.parse()?;
+        if ident == "null" {
+            input.parse::<syn::Ident>()?;
+            return Ok(quote! { ::valu3::prelude::Value::Null });
+        }
+    }
+
+    // The trait is imported in a block of its own so method lookup still auto-derefs
+    // references, without relying on the caller having it in scope.
+    let expr: syn::Expr = input.parse()?;
+    Ok(quote! {{
+        use ::valu3::prelude::ToValueBehavior as _;
+        (#expr).to_value()
+    }})
+}
+
+fn value_macro_object(input: syn::parse::ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    let mut inserts = Vec::new();
+    while !input.is_empty() {
+        let key = if input.peek(syn::LitStr) {
+            let key: syn::LitStr = input.parse()?;
+            quote! { ::std::string::ToString::to_string(#key) }
+        } else {
+            let key: syn::Expr = input.parse()?;
+            quote! { ::std::string::ToString::to_string(&(#key)) }
+        };
+        input.parse::<syn::Token![:]>()?;
+        let value = value_macro_value(input)?;
+        inserts.push(quote! { map.insert(#key, #value); });
+
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<syn::Token![,]>()?;
+    }
+
+    Ok(quote! {{
+        let mut map: ::std::collections::HashMap<::std::string::String, ::valu3::prelude::Value> =
+            ::std::collections::HashMap::new();
+        #(#inserts)*
+        ::valu3::prelude::Value::from(map)
+    }})
+}
+
+fn value_macro_array(input: syn::parse::ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    let mut items = Vec::new();
+    while !input.is_empty() {
+        items.push(value_macro_value(input)?);
+
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<syn::Token![,]>()?;
+    }
+
+    Ok(quote! {
+        ::valu3::prelude::Value::from(
+            ::std::vec![#(#items),*] as ::std::vec::Vec<::valu3::prelude::Value>
+        )
+    })
+}
@@ -0,0 +1,9 @@
+use valu3::prelude::*;
+
+#[derive(ToValue, FromValue)]
+enum Shape {
+    Point,
+    Circle { radius: f64 },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use valu3::prelude::*;
+
+#[derive(FromValue)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
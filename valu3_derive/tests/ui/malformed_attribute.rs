@@ -0,0 +1,9 @@
+use valu3::prelude::*;
+
+#[derive(FromValue)]
+#[valu3(deny_unknown_fields = 1)]
+struct Config {
+    name: String,
+}
+
+fn main() {}
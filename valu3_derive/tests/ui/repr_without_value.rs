@@ -0,0 +1,10 @@
+use valu3::prelude::*;
+
+#[derive(FromValue)]
+#[valu3(repr)]
+enum Color {
+    Red,
+    Green,
+}
+
+fn main() {}
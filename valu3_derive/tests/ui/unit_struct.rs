@@ -0,0 +1,6 @@
+use valu3::prelude::*;
+
+#[derive(FromValue)]
+struct Marker;
+
+fn main() {}
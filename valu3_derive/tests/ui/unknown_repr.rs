@@ -0,0 +1,10 @@
+use valu3::prelude::*;
+
+#[derive(ToValue, FromValue)]
+#[valu3(repr = "bogus")]
+enum Color {
+    Red,
+    Green,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use valu3::prelude::*;
+
+#[derive(ToValue)]
+struct Event {
+    #[valu3(with)]
+    at: u64,
+}
+
+fn main() {}